@@ -1,31 +1,20 @@
 use crate::{
     config::Config,
-    history::{ClipboardItem, History},
+    error::{Error, Result},
+    history::History,
 };
-use std::io::{self, ErrorKind, Write};
+use std::io::{self, Write};
 
 /// Entfernt alle Einträge aus der History und löscht zugehörige Bilddateien
-pub fn clear_history(history: &mut History, cfg: &Config) -> std::io::Result<()> {
+pub fn clear_history(history: &mut History, cfg: &Config) -> Result<()> {
     if ask_yes_no("Do you realy want to delete History?") {
         for entry in &history.entries {
-            if let ClipboardItem::Image(ref path) = entry.item {
-                if path.exists()
-                    && path.is_file()
-                    && (path.ends_with(".png") || path.ends_with(".jpg"))
-                {
-                    if let Err(e) = std::fs::remove_file(path) {
-                        eprintln!("⚠️  Konnte Bild nicht löschen {}: {e}", path.display());
-                    }
-                }
-            }
+            entry.remove_files();
         }
         history.clear();
-        if let Err(e) = history.save(&cfg.storage_path) {
-            eprintln!("⚠️  Fehler beim Speichern der History: {}", e);
-        }
-        return Ok(());
+        history.save(&cfg.storage_path)
     } else {
-        Err(io::Error::new(ErrorKind::Other, "User aborted"))
+        Err(Error::UserAbort)
     }
 }
 
@@ -36,7 +25,10 @@ fn ask_yes_no(question: &str) -> bool {
         io::stdout().flush().unwrap();
 
         let mut answer = String::new();
-        io::stdin().read_line(&mut answer).unwrap();
+        // Ende der Eingabe (z. B. Ctrl+D) gilt als „nein“
+        if io::stdin().read_line(&mut answer).unwrap_or(0) == 0 {
+            return false;
+        }
 
         match answer.trim().to_lowercase().as_str() {
             "y" | "yes" => return true,
@@ -1,20 +1,20 @@
-use crate::{clipboard_state, history::ClipboardItem, util::hash_data};
+use crate::{
+    clipboard_state,
+    error::{Error, Result},
+    history::ClipboardItem,
+    util::hash_data,
+};
 use wl_clipboard_rs::copy::{MimeType, Options, Source};
 
-pub fn set_clipboard_item(item: &ClipboardItem) -> Result<(), Box<dyn std::error::Error>> {
+pub fn set_clipboard_item(item: &ClipboardItem) -> Result<()> {
     set_clipboard_item_internal(item, true)
 }
 
-pub fn set_clipboard_item_no_ignore(
-    item: &ClipboardItem,
-) -> Result<(), Box<dyn std::error::Error>> {
+pub fn set_clipboard_item_no_ignore(item: &ClipboardItem) -> Result<()> {
     set_clipboard_item_internal(item, false)
 }
 
-fn set_clipboard_item_internal(
-    item: &ClipboardItem,
-    set_ignore: bool,
-) -> Result<(), Box<dyn std::error::Error>> {
+fn set_clipboard_item_internal(item: &ClipboardItem, set_ignore: bool) -> Result<()> {
     let opts = Options::new();
 
     match item {
@@ -28,7 +28,7 @@ fn set_clipboard_item_internal(
             )?;
         }
         ClipboardItem::Image(path) => {
-            let data = std::fs::read(path)?;
+            let data = std::fs::read(path).map_err(Error::storage(path))?;
             let hash = hash_data(&data);
             if set_ignore {
                 clipboard_state::set_skip_image_hash(hash);
@@ -0,0 +1,385 @@
+//! Zusammenführen der Konfigurationsebenen.
+//!
+//! Reihenfolge (spätere gewinnen): Standardwerte < `config.toml` < `--config <PATH>` <
+//! `HYPRCLIP_*`-Umgebungsvariablen < `--option KEY=WERT`.
+
+use super::Config;
+use crate::{
+    error::{Error, Result},
+    hooks,
+};
+use std::{collections::BTreeMap, fmt, fs, path::PathBuf};
+use toml::{Table, Value};
+use tracing::{debug, warn};
+
+/// Präfix aller Umgebungsvariablen, die Konfigurationswerte setzen
+const ENV_PREFIX: &str = "HYPRCLIP_";
+
+/// Herkunft eines wirksamen Werts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Default,
+    File(PathBuf),
+    Env(String),
+    Cli,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Default => write!(f, "Standard"),
+            Self::File(path) => write!(f, "Datei {}", path.display()),
+            Self::Env(var) => write!(f, "Umgebung {var}"),
+            Self::Cli => write!(f, "Kommandozeile"),
+        }
+    }
+}
+
+/// Ebenen aus der Kommandozeile, für alle späteren Ladevorgänge (auch Hot-Reload)
+#[derive(Debug, Clone, Default)]
+pub struct Overrides {
+    /// Zusätzliche Datei aus `--config`
+    pub config: Option<PathBuf>,
+    /// `--option KEY=WERT`, in Aufrufreihenfolge
+    pub options: Vec<(String, String)>,
+}
+
+/// Schlüsselpfad als einzelne Segmente; Segmente dürfen Punkte enthalten
+/// (`paste.per_class."org.wezfurlong.wezterm"`)
+pub type KeyPath = Vec<String>;
+
+/// Zusammengeführte Ebenen samt Herkunft je Schlüssel
+#[derive(Debug, Default)]
+pub struct Layered {
+    pub table: Table,
+    pub sources: BTreeMap<KeyPath, Source>,
+}
+
+impl Layered {
+    /// Herkunft eines Schlüssels; gesetzte Tabellen vererben ihre Herkunft
+    pub fn source_of(&self, key: &[String]) -> Source {
+        (0..=key.len())
+            .rev()
+            .find_map(|len| self.sources.get(&key[..len]))
+            .cloned()
+            .unwrap_or(Source::Default)
+    }
+}
+
+/// Führt Dateien, Umgebung und `--option`-Werte zusammen
+pub fn collect(
+    files: &[PathBuf],
+    env: impl IntoIterator<Item = (String, String)>,
+    options: &[(String, String)],
+) -> Result<Layered> {
+    let mut layered = Layered::default();
+    for path in files {
+        let contents = fs::read_to_string(path).map_err(Error::storage(path))?;
+        // Einzeln gegen das Schema prüfen, damit Tippfehler mit Zeile gemeldet werden
+        toml::from_str::<Config>(&contents)
+            .map_err(|e| Error::Config(format!("{}: {e}", path.display())))?;
+        let mut table: Table = toml::from_str(&contents)
+            .map_err(|e| Error::Config(format!("{}: {e}", path.display())))?;
+        if let Some(status) = table.remove("waybar") {
+            table.insert("status".to_string(), status);
+        }
+        merge(&mut layered, &[], table, &Source::File(path.clone()));
+    }
+
+    let defaults = Value::try_from(Config::default())
+        .map_err(|e| Error::Config(format!("Standardwerte: {e}")))?;
+    let known: BTreeMap<String, KeyPath> = leaves(&defaults)
+        .into_iter()
+        .filter_map(|(key, _)| Some((env_name(&key)?, key)))
+        .collect();
+    for (var, raw) in env {
+        if !var.starts_with(ENV_PREFIX) {
+            continue;
+        }
+        match known.get(&var) {
+            Some(key) => {
+                let value = parse_value(&raw, lookup(&defaults, key));
+                set(&mut layered, &defaults, key, value, Source::Env(var))?;
+            }
+            // Setzen die Hooks für ihre Kindprozesse, z. B. wenn ein Hook `hyprclip` aufruft
+            None if hooks::ENV_VARS.contains(&var.as_str()) => {
+                debug!("Umgebungsvariable {var} eines Hooks wird ignoriert");
+            }
+            None => warn!("Unbekannte Umgebungsvariable {var} wird ignoriert"),
+        }
+    }
+
+    for (key, raw) in options {
+        let key = parse_key(key)?;
+        let value = parse_value(raw, lookup(&defaults, &key));
+        set(&mut layered, &defaults, &key, value, Source::Cli)?;
+    }
+    Ok(layered)
+}
+
+/// Name der Umgebungsvariable für einen Schlüssel: `window.width` → `HYPRCLIP_WINDOW_WIDTH`.
+///
+/// Segmente mit anderen Zeichen als Buchstaben, Ziffern, `_` und `-` (z. B. Fensterklassen
+/// mit Punkten) haben keinen eindeutigen Namen und lassen sich nur per `--option` setzen.
+pub fn env_name(key: &[String]) -> Option<String> {
+    key.iter().all(|segment| is_bare(segment)).then(|| {
+        format!(
+            "{ENV_PREFIX}{}",
+            key.join("_").replace('-', "_").to_uppercase()
+        )
+    })
+}
+
+/// Schreibweise wie in TOML: `paste.per_class."org.wezfurlong.wezterm"`
+pub fn display_key(key: &[String]) -> String {
+    key.iter()
+        .map(|segment| {
+            if is_bare(segment) {
+                segment.clone()
+            } else {
+                Value::String(segment.clone()).to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Liest einen Schlüssel wie in TOML, Segmente mit Punkten in Anführungszeichen
+fn parse_key(key: &str) -> Result<KeyPath> {
+    let invalid = || Error::Invalid(format!("Ungültiger Schlüssel „{key}“"));
+    let table: Table = toml::from_str(&format!("{key} = 0")).map_err(|_| invalid())?;
+    let mut value = Value::Table(table);
+    let mut path = Vec::new();
+    while let Value::Table(table) = value {
+        if table.len() != 1 {
+            return Err(invalid());
+        }
+        let (segment, inner) = table.into_iter().next().ok_or_else(invalid)?;
+        path.push(segment);
+        value = inner;
+    }
+    if value != Value::Integer(0) {
+        return Err(invalid());
+    }
+    Ok(path)
+}
+
+fn is_bare(segment: &str) -> bool {
+    !segment.is_empty()
+        && segment
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Alle Blätter einer Tabelle mit ihrem Wert; Arrays zählen als Blatt
+pub fn leaves(value: &Value) -> Vec<(KeyPath, Value)> {
+    fn walk(prefix: &mut KeyPath, value: &Value, out: &mut Vec<(KeyPath, Value)>) {
+        match value {
+            Value::Table(table) => {
+                for (key, value) in table {
+                    prefix.push(key.clone());
+                    walk(prefix, value, out);
+                    prefix.pop();
+                }
+            }
+            _ => out.push((prefix.clone(), value.clone())),
+        }
+    }
+    let mut out = Vec::new();
+    walk(&mut Vec::new(), value, &mut out);
+    out
+}
+
+fn lookup<'a>(value: &'a Value, key: &[String]) -> Option<&'a Value> {
+    key.iter()
+        .try_fold(value, |value, part| value.get(part.as_str()))
+}
+
+/// Texte bleiben wörtlich, alles andere wird als TOML-Wert gelesen (`42`, `true`, `["a"]`)
+fn parse_value(raw: &str, default: Option<&Value>) -> Value {
+    if let Some(Value::String(_)) = default {
+        return Value::String(raw.to_string());
+    }
+    toml::from_str::<Table>(&format!("value = {raw}"))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| Value::String(raw.to_string()))
+}
+
+/// Tabellen werden zusammengeführt, alle anderen Werte ersetzt
+fn merge(layered: &mut Layered, prefix: &[String], layer: Table, source: &Source) {
+    for (key, value) in layer {
+        let mut path = prefix.to_vec();
+        path.push(key.clone());
+        let nested = lookup_mut(&mut layered.table, prefix)
+            .and_then(|table| table.get(&key))
+            .is_some_and(Value::is_table);
+        match value {
+            Value::Table(inner) if nested => merge(layered, &path, inner, source),
+            value => {
+                record(&mut layered.sources, &path, &value, source);
+                if let Some(table) = lookup_mut(&mut layered.table, prefix) {
+                    table.insert(key, value);
+                }
+            }
+        }
+    }
+}
+
+/// Setzt einen einzelnen Schlüssel. Fehlende Tabellen starten mit ihren Standardwerten,
+/// damit z. B. ein einzelner `paste.per_class`-Eintrag die übrigen nicht verdrängt.
+fn set(
+    layered: &mut Layered,
+    defaults: &Value,
+    key: &[String],
+    value: Value,
+    source: Source,
+) -> Result<()> {
+    let Some((last, parents)) = key.split_last() else {
+        return Err(Error::Invalid("Leerer Schlüssel".into()));
+    };
+    let mut table = &mut layered.table;
+    for (depth, part) in parents.iter().enumerate() {
+        table = table
+            .entry(part.as_str())
+            .or_insert_with(|| match lookup(defaults, &parents[..=depth]) {
+                Some(Value::Table(default)) => Value::Table(default.clone()),
+                _ => Value::Table(Table::new()),
+            })
+            .as_table_mut()
+            .ok_or_else(|| {
+                Error::Invalid(format!("{}: „{part}“ ist keine Tabelle", display_key(key)))
+            })?;
+    }
+    record(&mut layered.sources, key, &value, &source);
+    table.insert(last.clone(), value);
+    Ok(())
+}
+
+/// Merkt die Herkunft aller Blätter unter `key`; ältere Angaben darunter verfallen
+fn record(sources: &mut BTreeMap<KeyPath, Source>, key: &[String], value: &Value, source: &Source) {
+    sources.retain(|k, _| !k.starts_with(key));
+    if value.is_table() {
+        for (leaf, _) in leaves(value) {
+            sources.insert([key, &leaf].concat(), source.clone());
+        }
+    } else {
+        sources.insert(key.to_vec(), source.clone());
+    }
+}
+
+fn lookup_mut<'a>(table: &'a mut Table, prefix: &[String]) -> Option<&'a mut Table> {
+    prefix
+        .iter()
+        .try_fold(table, |table, part| table.get_mut(part)?.as_table_mut())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(key: &str) -> KeyPath {
+        parse_key(key).unwrap()
+    }
+
+    fn file(dir: &std::path::Path, name: &str, contents: &str) -> PathBuf {
+        let path = dir.join(name);
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn later_layers_win() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = file(
+            dir.path(),
+            "config.toml",
+            "history_limit = 5\n[window]\nwidth = 300.0\nheight = 200.0\n",
+        );
+        let extra = file(dir.path(), "extra.toml", "[window]\nwidth = 400.0\n");
+        let env = [
+            ("HYPRCLIP_HISTORY_LIMIT".to_string(), "7".to_string()),
+            (
+                "HYPRCLIP_STORAGE_PATH".to_string(),
+                "/tmp/test.json".to_string(),
+            ),
+            ("PATH".to_string(), "/usr/bin".to_string()),
+        ];
+        let options = [("history_limit".to_string(), "9".to_string())];
+        let layered = collect(&[base.clone(), extra.clone()], env, &options).unwrap();
+
+        let cfg = Config::from_table(layered.table.clone()).unwrap();
+        assert_eq!(cfg.history_limit, 9);
+        assert_eq!(cfg.window.width, 400.0);
+        assert_eq!(cfg.window.height, 200.0);
+        assert_eq!(cfg.storage_path, PathBuf::from("/tmp/test.json"));
+
+        assert_eq!(layered.source_of(&key("history_limit")), Source::Cli);
+        assert_eq!(layered.source_of(&key("window.width")), Source::File(extra));
+        assert_eq!(layered.source_of(&key("window.height")), Source::File(base));
+        assert_eq!(
+            layered.source_of(&key("storage_path")),
+            Source::Env("HYPRCLIP_STORAGE_PATH".to_string())
+        );
+        assert_eq!(layered.source_of(&key("theme")), Source::Default);
+    }
+
+    #[test]
+    fn values_are_typed_by_their_default() {
+        let env = [
+            ("HYPRCLIP_PASTE_DELAY_MS".to_string(), "25".to_string()),
+            (
+                "HYPRCLIP_SELECTION_MERGE_SEPARATOR".to_string(),
+                "42".to_string(),
+            ),
+        ];
+        let layered = collect(&[], env, &[]).unwrap();
+        let cfg = Config::from_table(layered.table).unwrap();
+        assert_eq!(cfg.paste.delay_ms, 25);
+        assert_eq!(cfg.selection.merge_separator, "42");
+    }
+
+    #[test]
+    fn env_names_follow_keys() {
+        assert_eq!(
+            env_name(&key("window.width")).unwrap(),
+            "HYPRCLIP_WINDOW_WIDTH"
+        );
+        assert_eq!(
+            env_name(&key("history_limit")).unwrap(),
+            "HYPRCLIP_HISTORY_LIMIT"
+        );
+        assert_eq!(
+            env_name(&key("paste.per_class.\"org.wezfurlong.wezterm\"")),
+            None
+        );
+    }
+
+    #[test]
+    fn dotted_map_keys_stay_one_segment() {
+        let options = [(
+            "paste.per_class.\"org.wezfurlong.wezterm\"".to_string(),
+            "Ctrl+v".to_string(),
+        )];
+        let env = [("HYPRCLIP_KIND".to_string(), "text".to_string())];
+        let layered = collect(&[], env, &options).unwrap();
+        let cfg = Config::from_table(layered.table.clone()).unwrap();
+        assert_eq!(
+            cfg.paste.per_class["org.wezfurlong.wezterm"].to_string(),
+            "Ctrl+v"
+        );
+        assert_eq!(
+            cfg.paste.per_class.len(),
+            Config::default().paste.per_class.len()
+        );
+
+        let class = key("paste.per_class.\"org.wezfurlong.wezterm\"");
+        assert_eq!(class.len(), 3);
+        assert_eq!(layered.source_of(&class), Source::Cli);
+        assert_eq!(
+            display_key(&class),
+            "paste.per_class.\"org.wezfurlong.wezterm\""
+        );
+        assert!(parse_key("paste.per_class.org wezterm").is_err());
+    }
+}
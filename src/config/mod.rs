@@ -0,0 +1,432 @@
+mod layers;
+mod watch;
+
+pub use layers::{Overrides, Source};
+pub use watch::ConfigWatcher;
+
+use crate::{
+    error::{Error, Result},
+    hooks::HooksConfig,
+    keymap::Keymap,
+    notifications::NotificationConfig,
+    paste::Keystroke,
+    queue::QueueConfig,
+    status::StatusConfig,
+    transform::CommandTransform,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, io, path::PathBuf, sync::OnceLock};
+use tracing::warn;
+
+/// Wirksamer Wert mit Schlüssel in TOML-Schreibweise (`window.width`) und Herkunft
+pub type SourcedValue = (String, toml::Value, Source);
+
+/// `--config` und `--option` aus der Kommandozeile, einmal beim Start gesetzt
+static OVERRIDES: OnceLock<Overrides> = OnceLock::new();
+
+/// Benutzerkonfiguration für Hyprclip; fehlende Felder erhalten ihren Standardwert,
+/// unbekannte Schlüssel sind ein Fehler
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Maximal gespeicherte Einträge im Clipboard-Verlauf
+    pub history_limit: usize,
+    /// Farbmodus für die UI
+    pub theme: Theme,
+    /// Pfad zur Datei, in der Text gespeichert wird
+    pub storage_path: PathBuf,
+    /// Pfad zur Datei, in der Bilder gespeichert werden
+    pub image_storage_path: PathBuf,
+    /// Größe und Position des Picker-Fensters
+    pub window: WindowConfig,
+    /// Tastenbelegung der GUI
+    pub keymap: Keymap,
+    /// Automatisches Einfügen nach der Auswahl
+    pub paste: PasteConfig,
+    /// Sammelaktionen für mehrere markierte Einträge
+    pub selection: SelectionConfig,
+    /// Eigene Umwandlungen als Befehls-Pipelines
+    pub transforms: Vec<CommandTransform>,
+    /// Eigene Skripte bei neuen Einträgen
+    pub hooks: HooksConfig,
+    /// Warteschlange zum Einfügen nacheinander
+    pub queue: QueueConfig,
+    /// Desktop-Benachrichtigungen des Watchers
+    pub notifications: NotificationConfig,
+    /// Text, Tooltip und Klick-Aktionen der Statusmodule (früher `[waybar]`)
+    #[serde(alias = "waybar")]
+    pub status: StatusConfig,
+}
+
+/// Einstellungen für Mehrfachauswahl (Zusammenführen, Export)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SelectionConfig {
+    /// Trenner zwischen zusammengeführten Texten
+    pub merge_separator: String,
+    /// Vorgabe-Verzeichnis für den Export
+    pub export_dir: PathBuf,
+}
+
+/// Geometrie und Verhalten des GUI-Fensters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WindowConfig {
+    /// Breite in logischen Pixeln
+    pub width: f32,
+    /// Höhe in logischen Pixeln
+    pub height: f32,
+    /// Wo das Fenster geöffnet wird
+    pub anchor: WindowAnchor,
+    /// Abstand zum Bildschirmrand bzw. zum Mauszeiger
+    pub margin: f32,
+    /// Schließt den Picker, sobald er den Fokus verliert
+    pub close_on_focus_loss: bool,
+}
+
+/// Ankerpunkt für das Picker-Fenster
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WindowAnchor {
+    /// Mitte des fokussierten Monitors
+    Center,
+    /// Oben mittig auf dem fokussierten Monitor
+    Top,
+    /// An der aktuellen Mausposition (Hyprland `cursorpos`)
+    Cursor,
+}
+
+/// Einstellungen für „Auswählen und Einfügen“
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PasteConfig {
+    /// Wie der Tastendruck erzeugt wird
+    pub backend: PasteBackend,
+    /// Standard-Tastenkombination zum Einfügen
+    pub keystroke: Keystroke,
+    /// Abweichende Tastenkombination je Fensterklasse (z. B. Terminals)
+    pub per_class: BTreeMap<String, Keystroke>,
+    /// Wie lange höchstens auf die Rückkehr des Fokus gewartet wird
+    pub focus_timeout_ms: u64,
+    /// Zusätzliche Pause, nachdem das Zielfenster den Fokus hat
+    pub delay_ms: u64,
+    /// Wie lange das Clipboard nach dem Setzen bzw. Tastendruck noch bedient wird
+    pub linger_ms: u64,
+}
+
+/// Mechanismus für den Einfüge-Tastendruck
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PasteBackend {
+    /// Virtuelle Tastatur, bei Fehlern `wtype`
+    Auto,
+    /// Wayland-Protokoll `zwp_virtual_keyboard_v1`
+    VirtualKeyboard,
+    /// Externes Programm `wtype`
+    Wtype,
+}
+
+impl Default for PasteConfig {
+    fn default() -> Self {
+        let terminal = Keystroke::parse("Ctrl+Shift+v").expect("gültige Tastenkombination");
+        let per_class = [
+            "kitty",
+            "Alacritty",
+            "foot",
+            "org.wezfurlong.wezterm",
+            "com.mitchellh.ghostty",
+        ]
+        .into_iter()
+        .map(|class| (class.to_string(), terminal.clone()))
+        .collect();
+
+        Self {
+            backend: PasteBackend::Auto,
+            keystroke: Keystroke::parse("Ctrl+v").expect("gültige Tastenkombination"),
+            per_class,
+            focus_timeout_ms: 1000,
+            delay_ms: 50,
+            linger_ms: 500,
+        }
+    }
+}
+
+impl Default for SelectionConfig {
+    fn default() -> Self {
+        Self {
+            merge_separator: "\n".to_string(),
+            export_dir: dirs::download_dir()
+                .or_else(dirs::home_dir)
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join("hyprclip-export"),
+        }
+    }
+}
+
+impl Default for WindowConfig {
+    fn default() -> Self {
+        Self {
+            width: 500.0,
+            height: 300.0,
+            anchor: WindowAnchor::Center,
+            margin: 12.0,
+            close_on_focus_loss: true,
+        }
+    }
+}
+
+/// Darstellungstypen für die GUI
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    Dark,
+    Light,
+    System,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            history_limit: 100,
+            theme: Theme::System,
+            storage_path: Self::default_storage_path(),
+            image_storage_path: Self::default_image_storage_path(),
+            window: WindowConfig::default(),
+            keymap: Keymap::default(),
+            paste: PasteConfig::default(),
+            selection: SelectionConfig::default(),
+            transforms: Vec::new(),
+            hooks: HooksConfig::default(),
+            queue: QueueConfig::default(),
+            notifications: NotificationConfig::default(),
+            status: StatusConfig::default(),
+        }
+    }
+}
+
+impl Config {
+    /// Gibt den Pfad zurück, unter dem die Konfigurationsdatei gespeichert ist
+    pub fn path() -> PathBuf {
+        dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("hyprclip")
+            .join("config.toml")
+    }
+
+    /// Definierter Standardpfad für den Clipboard-Verlauf
+    fn default_storage_path() -> PathBuf {
+        dirs::data_local_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("hyprclip")
+            .join("clipboard.json")
+    }
+
+    fn default_image_storage_path() -> PathBuf {
+        dirs::data_local_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("hyprclip")
+            .join("images")
+    }
+
+    /// Merkt sich `--config` und `--option` für alle Ladevorgänge dieses Prozesses
+    pub fn set_overrides(overrides: Overrides) {
+        if OVERRIDES.set(overrides).is_err() {
+            warn!("Konfigurations-Overrides wurden bereits gesetzt");
+        }
+    }
+
+    fn overrides() -> &'static Overrides {
+        OVERRIDES.get_or_init(Overrides::default)
+    }
+
+    /// Die mit `--config` angegebene Datei, sonst [`Config::path`]
+    pub fn primary_path() -> PathBuf {
+        Self::overrides().config.clone().unwrap_or_else(Self::path)
+    }
+
+    /// Alle Dateien, aus denen gelesen wird; die Standarddatei nur, wenn sie existiert
+    pub fn files() -> Vec<PathBuf> {
+        let default = Self::path();
+        default
+            .exists()
+            .then_some(default)
+            .into_iter()
+            .chain(Self::overrides().config.clone())
+            .collect()
+    }
+
+    /// Dateien, deren Änderung ein Neuladen auslöst (auch eine noch fehlende `config.toml`)
+    pub fn watch_paths() -> Vec<PathBuf> {
+        std::iter::once(Self::path())
+            .chain(Self::overrides().config.clone())
+            .collect()
+    }
+
+    /// Lädt die Konfiguration; beim ersten Start wird `config.toml` mit Standardwerten angelegt
+    /// (nicht mit `--config`, damit z. B. Container das Home-Verzeichnis unberührt lassen)
+    pub fn load_or_create() -> Result<Self> {
+        if Self::overrides().config.is_none() && !Self::path().exists() {
+            if let Err(e) = Self::default().save() {
+                warn!("Standardkonfiguration konnte nicht gespeichert werden: {e}");
+            }
+        }
+        Self::load()
+    }
+
+    /// Lädt und prüft alle Ebenen: Standardwerte < `config.toml` < `--config` <
+    /// `HYPRCLIP_*` < `--option`
+    pub fn load() -> Result<Self> {
+        Self::load_with_sources().map(|(cfg, _)| cfg)
+    }
+
+    /// Wie [`Config::load`], zusätzlich mit der Herkunft jedes wirksamen Werts
+    pub fn load_with_sources() -> Result<(Self, Vec<SourcedValue>)> {
+        let layered =
+            layers::collect(&Self::files(), std::env::vars(), &Self::overrides().options)?;
+        let cfg = Self::from_table(layered.table.clone()).map_err(Error::Config)?;
+        let effective = toml::Value::try_from(&cfg).map_err(|e| Error::Config(e.to_string()))?;
+        let sources = layers::leaves(&effective)
+            .into_iter()
+            .map(|(key, value)| {
+                let source = layered.source_of(&key);
+                (layers::display_key(&key), value, source)
+            })
+            .collect();
+        Ok((cfg, sources))
+    }
+
+    /// Baut die Konfiguration aus zusammengeführten Ebenen und prüft sie
+    fn from_table(table: toml::Table) -> Result<Self, String> {
+        let cfg = Self::deserialize(table).map_err(|e| e.to_string())?;
+        let problems = cfg.problems();
+        if problems.is_empty() {
+            Ok(cfg)
+        } else {
+            Err(problems.join("\n"))
+        }
+    }
+
+    /// Werte, die sich parsen lassen, aber nicht funktionieren würden
+    fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.history_limit == 0 {
+            problems.push("history_limit muss größer als 0 sein".to_string());
+        }
+        if self.queue.size == 0 {
+            problems.push("queue.size muss größer als 0 sein".to_string());
+        }
+        if self.window.width <= 0.0 || self.window.height <= 0.0 {
+            problems.push("window.width und window.height müssen größer als 0 sein".to_string());
+        }
+
+        for (name, path) in [
+            ("storage_path", &self.storage_path),
+            ("image_storage_path", &self.image_storage_path),
+            ("selection.export_dir", &self.selection.export_dir),
+        ] {
+            if path.as_os_str().is_empty() {
+                problems.push(format!("{name} ist leer"));
+            } else if path.starts_with("~") {
+                problems.push(format!(
+                    "{name}: „~“ wird nicht erweitert, bitte absoluten Pfad angeben ({})",
+                    path.display()
+                ));
+            } else if !path.is_absolute() {
+                problems.push(format!(
+                    "{name} muss ein absoluter Pfad sein ({})",
+                    path.display()
+                ));
+            }
+        }
+        if self.storage_path.is_dir() {
+            problems.push(format!(
+                "storage_path ist ein Verzeichnis, erwartet wird eine Datei ({})",
+                self.storage_path.display()
+            ));
+        }
+        if self.image_storage_path.is_file() {
+            problems.push(format!(
+                "image_storage_path ist eine Datei, erwartet wird ein Verzeichnis ({})",
+                self.image_storage_path.display()
+            ));
+        }
+        problems.extend(self.hooks.problems());
+        problems
+    }
+
+    /// Speichert die aktuelle Konfiguration in die Datei
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::primary_path();
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(path, toml::to_string_pretty(self).unwrap())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(contents: &str) -> Result<Config, String> {
+        Config::from_table(toml::from_str(contents).map_err(|e| e.to_string())?)
+    }
+
+    #[test]
+    fn missing_fields_keep_their_defaults() {
+        let cfg = parse("history_limit = 7\n[window]\nwidth = 640.0\n").unwrap();
+        assert_eq!(cfg.history_limit, 7);
+        assert_eq!(cfg.window.width, 640.0);
+        assert_eq!(cfg.window.height, WindowConfig::default().height);
+        assert_eq!(cfg.storage_path, Config::default().storage_path);
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        let err = parse("histroy_limit = 7\n").unwrap_err();
+        assert!(err.contains("histroy_limit"), "{err}");
+        let err = parse("[window]\nwidht = 1.0\n").unwrap_err();
+        assert!(err.contains("widht"), "{err}");
+    }
+
+    #[test]
+    fn invalid_values_are_reported_together() {
+        let err = parse("history_limit = 0\nstorage_path = \"~/clip.json\"\n").unwrap_err();
+        assert!(
+            err.contains("history_limit muss größer als 0 sein"),
+            "{err}"
+        );
+        assert!(err.contains("storage_path: „~“"), "{err}");
+    }
+
+    #[test]
+    fn broken_hooks_and_filters_are_rejected() {
+        let err = parse(
+            r#"
+            [[hooks.on_new]]
+            name = "links"
+            command = "true"
+            when = { regex = "(" }
+
+            [[hooks.filters]]
+            name = "geheim"
+            builtin = "trim-trailing-whitespace"
+            command = "cat"
+            on_error = "closed"
+
+            [[hooks.filters]]
+            when = { regex = "[" }
+            builtin = "normalize-line-endings"
+            "#,
+        )
+        .unwrap_err();
+        assert!(
+            err.contains("hooks.on_new[0] („links“): regex parse error"),
+            "{err}"
+        );
+        assert!(
+            err.contains("hooks.filters[0] („geheim“): genau eins von `command` oder `builtin`"),
+            "{err}"
+        );
+        assert!(err.contains("hooks.filters[1]: regex parse error"), "{err}");
+    }
+}
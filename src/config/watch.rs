@@ -0,0 +1,96 @@
+//! Hot-Reload: Änderungen an den Konfigurationsdateien erkennen, erst nach einer Pause laden.
+
+use super::Config;
+use crate::fswatch::FileWatcher;
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+use tracing::warn;
+
+/// Wartezeit nach der letzten Änderung, bevor neu geladen wird – Editoren, die an Ort und
+/// Stelle speichern, zeigen kurz eine leere Datei, die sonst als reine Standardwerte gälte
+pub const SETTLE_DELAY: Duration = Duration::from_millis(100);
+
+/// Beobachtet alle Dateien aus [`Config::watch_paths`] für Watcher und GUI
+pub struct ConfigWatcher {
+    watchers: Vec<FileWatcher>,
+    changed_at: Option<Instant>,
+}
+
+impl ConfigWatcher {
+    /// `on_change` läuft bei jeder Dateiänderung, z. B. um die GUI aufzuwecken
+    pub fn new(on_change: impl Fn() + Clone + Send + 'static) -> Self {
+        Self::for_paths(Config::watch_paths(), on_change)
+    }
+
+    fn for_paths(paths: Vec<PathBuf>, on_change: impl Fn() + Clone + Send + 'static) -> Self {
+        let watchers = paths
+            .iter()
+            .filter_map(|path| match FileWatcher::new(path, on_change.clone()) {
+                Ok(watcher) => Some(watcher),
+                Err(e) => {
+                    warn!(path = %path.display(), "Kein automatisches Neuladen: {e}");
+                    None
+                }
+            })
+            .collect();
+        Self {
+            watchers,
+            changed_at: None,
+        }
+    }
+
+    /// `true`, wenn sich eine Datei geändert hat und seit der letzten Änderung
+    /// [`SETTLE_DELAY`] vergangen ist; jede weitere Änderung startet die Wartezeit neu
+    pub fn poll(&mut self) -> bool {
+        // Alle Watcher leeren, nicht nur bis zum ersten Treffer
+        if self.watchers.iter().filter(|w| w.take_changed()).count() > 0 {
+            self.changed_at = Some(Instant::now());
+        }
+        match self.changed_at {
+            Some(at) if at.elapsed() >= SETTLE_DELAY => {
+                self.changed_at = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Restliche Wartezeit einer erkannten Änderung, damit die GUI rechtzeitig neu zeichnet
+    pub fn pending(&self) -> Option<Duration> {
+        self.changed_at
+            .map(|at| SETTLE_DELAY.saturating_sub(at.elapsed()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs, thread};
+
+    #[test]
+    fn reloads_only_after_the_file_settles() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let mut watcher = ConfigWatcher::for_paths(vec![path.clone()], || {});
+        assert!(!watcher.poll());
+
+        // Leere Zwischenstufe wie beim Speichern an Ort und Stelle
+        let written = Instant::now();
+        fs::write(&path, "").unwrap();
+        assert!((0..50).any(|_| {
+            thread::sleep(Duration::from_millis(10));
+            !watcher.poll() && watcher.pending().is_some()
+        }));
+        assert!(!watcher.poll());
+
+        assert!((0..50).any(|_| {
+            thread::sleep(Duration::from_millis(20));
+            watcher.poll()
+        }));
+        assert!(written.elapsed() >= SETTLE_DELAY);
+        assert!(!watcher.poll());
+        assert_eq!(watcher.pending(), None);
+    }
+}
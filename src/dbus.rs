@@ -0,0 +1,467 @@
+use crate::{
+    clipboard,
+    config::Config,
+    error::Result,
+    fswatch::FileWatcher,
+    history::{ClipboardItem, History},
+    queue::Queue,
+    state::WatcherState,
+};
+use std::{
+    collections::HashSet,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::sync::{mpsc, watch};
+use tracing::warn;
+use zbus::{fdo, interface, object_server::SignalContext, Connection};
+
+/// Busname und Objektpfad des Watchers
+pub const SERVICE: &str = "org.hyprclip.Manager";
+pub const PATH: &str = "/org/hyprclip/Manager";
+
+/// Zeichen der Kurzfassung in `List` und `EntryAdded`
+const SUMMARY_CHARS: usize = 80;
+/// Wartezeit nach einer Dateiänderung, bevor Signale verschickt werden
+const SETTLE_DELAY: Duration = Duration::from_millis(100);
+
+/// Eintrag in `List`: ID (0 = neuester), Schlüssel, Art, Kurzfassung, Zeitstempel, angepinnt
+type EntryInfo = (u32, u64, String, String, u64, bool);
+
+/// `org.hyprclip.Manager`: Zugriff auf Verlauf und Zustand des laufenden Watchers.
+///
+/// Alle Methoden arbeiten auf der History-Datei, damit Änderungen von CLI und GUI
+/// sofort sichtbar sind; IDs entsprechen denen der CLI. Pfade kommen aus der jeweils
+/// aktuellen Konfiguration des Watchers, auch nach einem Hot-Reload.
+pub struct Manager {
+    history: Arc<Mutex<History>>,
+    config: watch::Receiver<Config>,
+}
+
+impl Manager {
+    fn storage_path(&self) -> PathBuf {
+        self.config.borrow().storage_path.clone()
+    }
+
+    fn load(&self) -> History {
+        let limit = self.config.borrow().history_limit;
+        History::load(&self.storage_path(), limit)
+    }
+
+    fn store(&self, history: History) -> fdo::Result<()> {
+        history
+            .save(&self.storage_path())
+            .map_err(|e| fdo::Error::IOError(e.to_string()))?;
+        *self.history.lock().unwrap() = history;
+        Ok(())
+    }
+
+    fn set_paused(&self, paused: bool) -> fdo::Result<()> {
+        WatcherState::update(&WatcherState::path(&self.storage_path()), |s| {
+            s.paused = paused
+        })
+        .map(|_| ())
+        .map_err(|e| fdo::Error::IOError(e.to_string()))
+    }
+}
+
+fn no_entry(id: u32) -> fdo::Error {
+    fdo::Error::InvalidArgs(format!("Kein Eintrag mit ID {id}"))
+}
+
+#[interface(name = "org.hyprclip.Manager")]
+impl Manager {
+    /// Alle Einträge, neuester zuerst
+    fn list(&self) -> Vec<EntryInfo> {
+        self.load()
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(id, e)| {
+                (
+                    id as u32,
+                    e.key(),
+                    e.kind.to_string(),
+                    e.summary(SUMMARY_CHARS),
+                    e.timestamp,
+                    e.pinned,
+                )
+            })
+            .collect()
+    }
+
+    /// Art und vollständiger Inhalt; bei Bildern der Dateipfad
+    fn get(&self, id: u32) -> fdo::Result<(String, String)> {
+        let history = self.load();
+        let entry = history
+            .entries
+            .get(id as usize)
+            .ok_or_else(|| no_entry(id))?;
+        let content = match &entry.item {
+            ClipboardItem::Text(text) => text.clone(),
+            ClipboardItem::Image(path) => path.display().to_string(),
+        };
+        Ok((entry.kind.to_string(), content))
+    }
+
+    /// Setzt den Eintrag ins Clipboard und schiebt ihn nach oben
+    fn select(&self, id: u32) -> fdo::Result<()> {
+        let mut history = self.load();
+        let entry = history.promote(id as usize).ok_or_else(|| no_entry(id))?;
+        self.store(history)?;
+        clipboard::set_clipboard_item_no_ignore(&entry.item)
+            .map_err(|e| fdo::Error::Failed(e.to_string()))
+    }
+
+    /// Entfernt den Eintrag samt Bilddateien
+    fn delete(&self, id: u32) -> fdo::Result<()> {
+        let mut history = self.load();
+        let entry = history
+            .entries
+            .get(id as usize)
+            .cloned()
+            .ok_or_else(|| no_entry(id))?;
+        history.delete_entry(id as usize);
+        entry.remove_files();
+        self.store(history)
+    }
+
+    fn pause(&self) -> fdo::Result<()> {
+        self.set_paused(true)
+    }
+
+    fn resume(&self) -> fdo::Result<()> {
+        self.set_paused(false)
+    }
+
+    #[zbus(property)]
+    fn paused(&self) -> bool {
+        WatcherState::load(&WatcherState::path(&self.storage_path())).paused
+    }
+
+    #[zbus(signal)]
+    async fn entry_added(
+        ctxt: &SignalContext<'_>,
+        id: u32,
+        key: u64,
+        kind: &str,
+        summary: &str,
+    ) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    async fn entry_removed(ctxt: &SignalContext<'_>, key: u64) -> zbus::Result<()>;
+
+    /// Pausiert, vertraulicher Inhalt im Clipboard, Länge der Warteschlange
+    #[zbus(signal)]
+    async fn state_changed(
+        ctxt: &SignalContext<'_>,
+        paused: bool,
+        sensitive: bool,
+        queue: u32,
+    ) -> zbus::Result<()>;
+}
+
+/// Läuft, solange der Wert lebt: beantwortet Aufrufe und meldet Änderungen als Signale
+pub struct Service {
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl Drop for Service {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Veröffentlicht den Manager auf `connection` und beansprucht [`SERVICE`].
+///
+/// `config` liefert die jeweils aktuelle Konfiguration; nach einem Reload folgen Methoden
+/// und Signale dem neuen Speicherort.
+pub async fn serve(
+    connection: Connection,
+    history: Arc<Mutex<History>>,
+    mut config: watch::Receiver<Config>,
+) -> Result<Service> {
+    // Signale aus Dateiänderungen ableiten – so lösen auch CLI und GUI sie aus
+    let (tx, rx) = mpsc::unbounded_channel();
+    let watchers = watch_files(&config.borrow_and_update(), &tx)?;
+
+    let manager = Manager {
+        history,
+        config: config.clone(),
+    };
+    connection.object_server().at(PATH, manager).await?;
+    connection.request_name(SERVICE).await?;
+
+    let task = tokio::spawn(emit_signals(connection, config, watchers, tx, rx));
+    Ok(Service { task })
+}
+
+// History-, Zustands- und Warteschlangendatei der Konfiguration beobachten
+fn watch_files(cfg: &Config, tx: &mpsc::UnboundedSender<()>) -> Result<Vec<FileWatcher>> {
+    let state_path = WatcherState::path(&cfg.storage_path);
+    let queue_path = Queue::path(&cfg.storage_path);
+    [&cfg.storage_path, &state_path, &queue_path]
+        .into_iter()
+        .map(|path| {
+            let tx = tx.clone();
+            FileWatcher::new(path, move || {
+                let _ = tx.send(());
+            })
+        })
+        .collect()
+}
+
+type Snapshot = (History, (bool, bool, u32));
+
+fn snapshot(cfg: &Config) -> Snapshot {
+    let history = History::load(&cfg.storage_path, cfg.history_limit);
+    let state = WatcherState::load(&WatcherState::path(&cfg.storage_path));
+    let queue = Queue::load(&Queue::path(&cfg.storage_path)).map_or(0, |q| q.depth() as u32);
+    (history, (state.paused, state.sensitive, queue))
+}
+
+async fn emit_signals(
+    connection: Connection,
+    mut config: watch::Receiver<Config>,
+    mut _watchers: Vec<FileWatcher>,
+    tx: mpsc::UnboundedSender<()>,
+    mut rx: mpsc::UnboundedReceiver<()>,
+) {
+    let Ok(ctxt) = SignalContext::new(&connection, PATH) else {
+        return;
+    };
+    let mut cfg = config.borrow().clone();
+    let (mut history, mut state) = snapshot(&cfg);
+    // Ohne Sender (Watcher beendet) bleibt die Konfiguration, wie sie ist
+    let mut reloads = true;
+
+    loop {
+        tokio::select! {
+            changed = rx.recv() => {
+                if changed.is_none() {
+                    return;
+                }
+                tokio::time::sleep(SETTLE_DELAY).await;
+            }
+            changed = config.changed(), if reloads => {
+                if changed.is_err() {
+                    reloads = false;
+                    continue;
+                }
+                // Neuer Speicherort: neue Dateien beobachten, Unterschiede als Signale melden
+                cfg = config.borrow_and_update().clone();
+                match watch_files(&cfg, &tx) {
+                    Ok(new) => _watchers = new,
+                    Err(e) => warn!("D-Bus-Signale folgen der neuen Konfiguration nicht: {e}"),
+                }
+            }
+        }
+        while rx.try_recv().is_ok() {}
+
+        let (new_history, new_state) = snapshot(&cfg);
+        let old_keys: HashSet<u64> = history.entries.iter().map(|e| e.key()).collect();
+        let new_keys: HashSet<u64> = new_history.entries.iter().map(|e| e.key()).collect();
+
+        for (id, entry) in new_history.entries.iter().enumerate() {
+            if !old_keys.contains(&entry.key()) {
+                let kind = entry.kind.to_string();
+                let summary = entry.summary(SUMMARY_CHARS);
+                let _ = Manager::entry_added(&ctxt, id as u32, entry.key(), &kind, &summary).await;
+            }
+        }
+        for key in old_keys.difference(&new_keys) {
+            let _ = Manager::entry_removed(&ctxt, *key).await;
+        }
+        if new_state != state {
+            let (paused, sensitive, queue) = new_state;
+            let _ = Manager::state_changed(&ctxt, paused, sensitive, queue).await;
+        }
+
+        history = new_history;
+        state = new_state;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader},
+        process::{Child, Command, Stdio},
+    };
+    use zbus::export::futures_util::StreamExt;
+
+    /// Privater Session-Bus, wird beim Drop beendet
+    struct TestBus {
+        daemon: Child,
+        address: String,
+    }
+
+    impl TestBus {
+        fn start() -> Option<Self> {
+            let mut daemon = Command::new("dbus-daemon")
+                .args(["--session", "--nofork", "--print-address=1"])
+                .stdout(Stdio::piped())
+                .stderr(Stdio::null())
+                .spawn()
+                .ok()?;
+            let mut address = String::new();
+            BufReader::new(daemon.stdout.take()?)
+                .read_line(&mut address)
+                .ok()?;
+            Some(Self {
+                daemon,
+                address: address.trim().to_string(),
+            })
+        }
+
+        async fn connect(&self) -> Connection {
+            zbus::connection::Builder::address(self.address.as_str())
+                .unwrap()
+                .build()
+                .await
+                .unwrap()
+        }
+    }
+
+    impl Drop for TestBus {
+        fn drop(&mut self) {
+            let _ = self.daemon.kill();
+            let _ = self.daemon.wait();
+        }
+    }
+
+    #[tokio::test]
+    async fn serves_history_and_emits_signals() {
+        let Some(bus) = TestBus::start() else {
+            eprintln!("dbus-daemon nicht verfügbar – Test übersprungen");
+            return;
+        };
+        let dir = tempfile::tempdir().unwrap();
+        let cfg = Config {
+            storage_path: dir.path().join("clipboard.json"),
+            ..Config::default()
+        };
+        let mut history = History::new(cfg.history_limit);
+        history.add_text("alt".into());
+        history.add_text("neu".into());
+        history.save(&cfg.storage_path).unwrap();
+
+        let shared = Arc::new(Mutex::new(History::new(cfg.history_limit)));
+        let (_reloads, config) = watch::channel(cfg.clone());
+        let _service = serve(bus.connect().await, shared, config).await.unwrap();
+
+        let client = bus.connect().await;
+        let proxy = zbus::Proxy::new(&client, SERVICE, PATH, SERVICE)
+            .await
+            .unwrap();
+
+        let list: Vec<EntryInfo> = proxy.call("List", &()).await.unwrap();
+        let summaries: Vec<&str> = list.iter().map(|e| e.3.as_str()).collect();
+        assert_eq!(summaries, ["neu", "alt"]);
+
+        let (kind, content): (String, String) = proxy.call("Get", &(1u32,)).await.unwrap();
+        assert_eq!((kind.as_str(), content.as_str()), ("text", "alt"));
+        assert!(proxy
+            .call::<_, _, (String, String)>("Get", &(9u32,))
+            .await
+            .is_err());
+
+        let mut added = proxy.receive_signal("EntryAdded").await.unwrap();
+        let mut removed = proxy.receive_signal("EntryRemoved").await.unwrap();
+        let mut state = proxy.receive_signal("StateChanged").await.unwrap();
+        let timeout = Duration::from_secs(5);
+
+        // Änderung von außen (wie durch den Watcher) → EntryAdded
+        history.add_text("ganz neu".into());
+        history.save(&cfg.storage_path).unwrap();
+        let msg = tokio::time::timeout(timeout, added.next())
+            .await
+            .unwrap()
+            .unwrap();
+        let (id, _key, _kind, summary): (u32, u64, String, String) =
+            msg.body().deserialize().unwrap();
+        assert_eq!((id, summary.as_str()), (0, "ganz neu"));
+
+        let () = proxy.call("Delete", &(0u32,)).await.unwrap();
+        tokio::time::timeout(timeout, removed.next())
+            .await
+            .unwrap()
+            .unwrap();
+        let list: Vec<EntryInfo> = proxy.call("List", &()).await.unwrap();
+        assert_eq!(list.len(), 2);
+
+        let () = proxy.call("Pause", &()).await.unwrap();
+        let msg = tokio::time::timeout(timeout, state.next())
+            .await
+            .unwrap()
+            .unwrap();
+        let (paused, _, _): (bool, bool, u32) = msg.body().deserialize().unwrap();
+        assert!(paused);
+        assert!(proxy.get_property::<bool>("Paused").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn follows_reloaded_storage_path() {
+        let Some(bus) = TestBus::start() else {
+            eprintln!("dbus-daemon nicht verfügbar – Test übersprungen");
+            return;
+        };
+        let dir = tempfile::tempdir().unwrap();
+        let old = Config {
+            storage_path: dir.path().join("alt/clipboard.json"),
+            ..Config::default()
+        };
+        let new = Config {
+            storage_path: dir.path().join("neu/clipboard.json"),
+            ..Config::default()
+        };
+        let mut old_history = History::new(old.history_limit);
+        old_history.add_text("aus alter Datei".into());
+        old_history.save(&old.storage_path).unwrap();
+        let mut new_history = History::new(new.history_limit);
+        new_history.add_text("aus neuer Datei".into());
+        new_history.save(&new.storage_path).unwrap();
+
+        let shared = Arc::new(Mutex::new(History::new(old.history_limit)));
+        let (reloads, config) = watch::channel(old.clone());
+        let _service = serve(bus.connect().await, shared, config).await.unwrap();
+
+        let client = bus.connect().await;
+        let proxy = zbus::Proxy::new(&client, SERVICE, PATH, SERVICE)
+            .await
+            .unwrap();
+        let mut added = proxy.receive_signal("EntryAdded").await.unwrap();
+        let mut state = proxy.receive_signal("StateChanged").await.unwrap();
+        let timeout = Duration::from_secs(5);
+
+        reloads.send_replace(new.clone());
+        let list: Vec<EntryInfo> = proxy.call("List", &()).await.unwrap();
+        let summaries: Vec<&str> = list.iter().map(|e| e.3.as_str()).collect();
+        assert_eq!(summaries, ["aus neuer Datei"]);
+
+        // Erst die Einträge der neuen Datei, dann ein neuer Eintrag darin
+        let mut next_summary = async || {
+            let msg = tokio::time::timeout(timeout, added.next())
+                .await
+                .unwrap()
+                .unwrap();
+            let (_, _, _, summary): (u32, u64, String, String) = msg.body().deserialize().unwrap();
+            summary
+        };
+        assert_eq!(next_summary().await, "aus neuer Datei");
+        new_history.add_text("nach dem Reload".into());
+        new_history.save(&new.storage_path).unwrap();
+        assert_eq!(next_summary().await, "nach dem Reload");
+
+        // Zustand liegt neben der neuen History
+        let () = proxy.call("Pause", &()).await.unwrap();
+        tokio::time::timeout(timeout, state.next())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(WatcherState::load(&WatcherState::path(&new.storage_path)).paused);
+        assert!(!WatcherState::load(&WatcherState::path(&old.storage_path)).paused);
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{
+    clipboard,
+    config::Config,
+    error::{Error, Result},
+    history::{ClipboardItem, History},
+};
+use std::{io::Write, path::Path, process::Command, sync::Mutex, time::Duration};
+
+/// Öffnet einen Texteintrag in `$EDITOR` und speichert das Ergebnis.
+///
+/// Ohne `replace` entsteht ein neuer Eintrag (Duplikate wie bei [`History::add_text`]),
+/// mit `replace` wird der Eintrag an Ort und Stelle ersetzt. Das Ergebnis landet im Clipboard.
+pub fn edit_entry(history: &Mutex<History>, cfg: &Config, id: usize, replace: bool) -> Result<()> {
+    let (text, suffix, key) = {
+        let guard = history.lock().unwrap();
+        let entry = guard
+            .entries
+            .get(id)
+            .ok_or_else(|| Error::NotFound(format!("Kein Eintrag mit ID {id}")))?;
+        let ClipboardItem::Text(text) = &entry.item else {
+            return Err(Error::Invalid(
+                "Nur Texteinträge können bearbeitet werden".into(),
+            ));
+        };
+        let suffix = format!(".{}", entry.kind.language().unwrap_or("txt"));
+        (text.clone(), suffix, entry.key())
+    };
+
+    let edited = edit_in_editor(&text, &suffix)?;
+    if edited == text {
+        println!("✏️  Keine Änderungen.");
+        return Ok(());
+    }
+
+    // Der Editor kann lange offen gewesen sein: frisch laden, damit Einträge des Watchers
+    // nicht verloren gehen, und den Eintrag über seinen Schlüssel statt die ID suchen
+    let entry = {
+        let mut guard = history.lock().unwrap();
+        let mut current = History::load(&cfg.storage_path, guard.limit);
+        let entry = if replace {
+            let index = current
+                .entries
+                .iter()
+                .position(|e| e.key() == key)
+                .ok_or_else(|| {
+                    Error::NotFound(format!("Eintrag {id} wurde inzwischen entfernt"))
+                })?;
+            current.replace_text(index, edited)
+        } else {
+            current.add_text(edited);
+            current.entries.first().cloned()
+        }
+        .ok_or_else(|| Error::NotFound(format!("Kein Eintrag mit ID {id}")))?;
+        current.save(&cfg.storage_path)?;
+        *guard = current;
+        entry
+    };
+
+    clipboard::set_clipboard_item_no_ignore(&entry.item)?;
+    println!("✏️  Eintrag gespeichert und ins Clipboard gelegt.");
+
+    // Wie bei `copy`: dem Watcher Zeit geben, den Clipboard-Inhalt zu übernehmen
+    std::thread::sleep(Duration::from_millis(cfg.paste.linger_ms));
+    Ok(())
+}
+
+/// Schreibt den Text in eine temporäre Datei, öffnet `$VISUAL`/`$EDITOR` (sonst `vi`) und liest sie zurück
+fn edit_in_editor(text: &str, suffix: &str) -> Result<String> {
+    let mut file = tempfile::Builder::new()
+        .prefix("hyprclip-")
+        .suffix(suffix)
+        .tempfile()?;
+    file.write_all(text.as_bytes())?;
+    file.flush()?;
+
+    open_editor(file.path())?;
+
+    let edited = std::fs::read_to_string(file.path())?;
+    // Viele Editoren hängen beim Speichern einen Zeilenumbruch an
+    Ok(match edited.strip_suffix('\n') {
+        Some(stripped) if !text.ends_with('\n') => stripped.to_string(),
+        _ => edited,
+    })
+}
+
+/// Öffnet `path` in `$VISUAL`/`$EDITOR` (sonst `vi`) und wartet, bis der Editor beendet ist
+pub fn open_editor(path: &Path) -> Result<()> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    // Editoren mit Argumenten, z. B. "code --wait"
+    let mut parts = editor.split_whitespace();
+    let program = parts
+        .next()
+        .ok_or_else(|| Error::Invalid("Kein Editor gesetzt".into()))?;
+
+    let status = Command::new(program)
+        .args(parts)
+        .arg(path)
+        .status()
+        .map_err(|e| {
+            Error::Command(format!(
+                "Editor „{program}“ konnte nicht gestartet werden: {e}"
+            ))
+        })?;
+    if !status.success() {
+        return Err(Error::Command(format!(
+            "Editor „{program}“ beendet mit {status}"
+        )));
+    }
+    Ok(())
+}
@@ -1,2 +1,122 @@
-/// Convenient alias for results used across the crate.
-pub type AnyResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
+use std::{
+    io,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+/// Ergebnis mit dem Fehlertyp von Hyprclip
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Alle Fehler, die bis zu `main` durchgereicht werden.
+///
+/// Jede Art hat einen eigenen Exit-Code (siehe [`Error::exit_code`]), damit Skripte
+/// z. B. „Eintrag fehlt“ von „Clipboard nicht erreichbar“ unterscheiden können.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// Falscher Aufruf, ungültige Eingabe oder Eintrag der falschen Art
+    #[error("{0}")]
+    Invalid(String),
+    /// Eintrag, Umwandlung, Snippet o. Ä. existiert nicht
+    #[error("{0}")]
+    NotFound(String),
+    /// Wayland-Clipboard nicht les- oder setzbar
+    #[error("Clipboard: {0}")]
+    Clipboard(String),
+    /// Datei (Verlauf, Zustand, Warteschlange, …) nicht les- oder schreibbar
+    #[error("{}: {source}", path.display())]
+    Storage {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    /// Sonstiger E/A-Fehler ohne bestimmte Datei
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// Konfiguration oder `snippets.toml` fehlerhaft
+    #[error("Konfiguration: {0}")]
+    Config(String),
+    /// Bild nicht dekodier- oder speicherbar
+    #[error("Bild: {0}")]
+    Image(#[from] image::ImageError),
+    /// D-Bus oder Dateiüberwachung nicht verfügbar
+    #[error("IPC: {0}")]
+    Ipc(String),
+    /// Tastendruck konnte nicht gesendet werden
+    #[error("Einfügen: {0}")]
+    Paste(String),
+    /// Externes Programm (Editor, Umwandlung, Hook, GUI) fehlgeschlagen
+    #[error("{0}")]
+    Command(String),
+    /// Vom Benutzer abgebrochen
+    #[error("Abgebrochen")]
+    UserAbort,
+}
+
+impl Error {
+    /// Für `map_err`: E/A-Fehler mit der betroffenen Datei
+    pub fn storage(path: &Path) -> impl FnOnce(io::Error) -> Self + '_ {
+        move |source| Self::Storage {
+            path: path.to_path_buf(),
+            source,
+        }
+    }
+
+    /// Exit-Code des Prozesses; 2 entspricht den Aufruffehlern von clap
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Self::Command(_) => 1,
+            Self::Invalid(_) => 2,
+            Self::NotFound(_) => 3,
+            Self::Clipboard(_) => 4,
+            Self::Storage { .. } | Self::Io(_) => 5,
+            Self::Config(_) => 6,
+            Self::Image(_) => 7,
+            Self::Ipc(_) => 8,
+            Self::Paste(_) => 9,
+            Self::UserAbort => 130,
+        }
+    }
+}
+
+impl From<Error> for ExitCode {
+    fn from(error: Error) -> Self {
+        ExitCode::from(error.exit_code())
+    }
+}
+
+impl From<wl_clipboard_rs::copy::Error> for Error {
+    fn from(error: wl_clipboard_rs::copy::Error) -> Self {
+        Self::Clipboard(error.to_string())
+    }
+}
+
+impl From<wl_clipboard_rs::paste::Error> for Error {
+    fn from(error: wl_clipboard_rs::paste::Error) -> Self {
+        Self::Clipboard(error.to_string())
+    }
+}
+
+impl From<zbus::Error> for Error {
+    fn from(error: zbus::Error) -> Self {
+        Self::Ipc(error.to_string())
+    }
+}
+
+impl From<notify::Error> for Error {
+    fn from(error: notify::Error) -> Self {
+        Self::Ipc(format!("Dateiüberwachung: {error}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn storage_errors_name_the_file() {
+        let error = Error::storage(Path::new("/tmp/clipboard.json"))(io::Error::other("voll"));
+        assert_eq!(error.to_string(), "/tmp/clipboard.json: voll");
+        assert_eq!(error.exit_code(), 5);
+        assert_eq!(Error::UserAbort.exit_code(), 130);
+    }
+}
@@ -0,0 +1,119 @@
+use crate::error::Result;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::{
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// Meldet Änderungen an einer einzelnen Datei (inotify unter Linux).
+///
+/// Beobachtet wird das übergeordnete Verzeichnis, damit auch neu angelegte oder per
+/// Umbenennen ersetzte Dateien erkannt werden.
+pub struct FileWatcher {
+    _watcher: RecommendedWatcher,
+    changed: Arc<AtomicBool>,
+}
+
+impl FileWatcher {
+    /// `on_change` läuft auf dem Thread des Watchers, z. B. um die GUI aufzuwecken
+    pub fn new(path: &Path, on_change: impl Fn() + Send + 'static) -> Result<Self> {
+        let target = path.to_path_buf();
+        let dir = target
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| PathBuf::from("."));
+        std::fs::create_dir_all(&dir)?;
+
+        let changed = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&changed);
+        let mut watcher =
+            notify::recommended_watcher(move |res: notify::Result<Event>| match res {
+                Ok(event) if is_relevant(&event, &target) => {
+                    flag.store(true, Ordering::SeqCst);
+                    on_change();
+                }
+                Ok(_) => {}
+                Err(e) => tracing::warn!("Fehler beim Beobachten von Dateien: {e}"),
+            })?;
+        watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+
+        Ok(Self {
+            _watcher: watcher,
+            changed,
+        })
+    }
+
+    /// Liefert `true`, wenn sich die Datei seit dem letzten Aufruf geändert hat
+    pub fn take_changed(&self) -> bool {
+        self.changed.swap(false, Ordering::SeqCst)
+    }
+}
+
+/// Schreibt eine Datei atomar: erst eine temporäre Datei im selben Verzeichnis, dann
+/// `rename`. Wer auf Änderungen reagiert, sieht nie eine leere oder halbe Datei.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let dir = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    std::fs::create_dir_all(dir)?;
+    let mut file = tempfile::NamedTempFile::new_in(dir)?;
+    file.write_all(contents)?;
+    file.persist(path).map_err(|e| e.error)?;
+    Ok(())
+}
+
+// Nur ein Verzeichnis ohne Rekursion → Dateiname genügt (unabhängig von Symlinks im Pfad)
+fn is_relevant(event: &Event, target: &Path) -> bool {
+    !matches!(event.kind, EventKind::Access(_))
+        && event
+            .paths
+            .iter()
+            .any(|p| p.file_name() == target.file_name())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{thread, time::Duration};
+
+    fn wait_for(watcher: &FileWatcher) -> bool {
+        (0..50).any(|_| {
+            thread::sleep(Duration::from_millis(20));
+            watcher.take_changed()
+        })
+    }
+
+    #[test]
+    fn reports_changes_to_watched_file_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("history.json");
+        let watcher = FileWatcher::new(&file, || {}).unwrap();
+
+        std::fs::write(dir.path().join("other.json"), "{}").unwrap();
+        thread::sleep(Duration::from_millis(200));
+        assert!(!watcher.take_changed());
+
+        std::fs::write(&file, "{}").unwrap();
+        assert!(wait_for(&watcher));
+        assert!(!watcher.take_changed());
+    }
+
+    #[test]
+    fn atomic_writes_replace_the_file_and_are_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("history.json");
+        std::fs::write(&file, "alt").unwrap();
+        let watcher = FileWatcher::new(&file, || {}).unwrap();
+
+        write_atomic(&file, b"neu").unwrap();
+        assert!(wait_for(&watcher));
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "neu");
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+}
@@ -1,8 +1,13 @@
+use crate::error::{Error, Result};
+use crate::fswatch;
+use crate::kind::{self, ContentKind};
+use crate::thumbnail;
 use crate::util::hash_data;
 use serde::{Deserialize, Serialize};
 use std::fs::{self, File};
-use std::io::{BufReader, Write};
-use std::path::PathBuf;
+use std::io::{self, BufReader};
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum ClipboardItem {
@@ -11,11 +16,116 @@ pub enum ClipboardItem {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(from = "StoredEntry")]
 pub struct Entry {
     pub content: String, // Vorschau (z. B. "🖼 Bild gespeichert...")
     pub timestamp: u64,
     pub item: ClipboardItem, // NEU: Für das tatsächliche Clipboard-Setzen
     pub hash: Option<u64>,   // ✅ NEU: für persistente Duplicate-Erkennung
+    #[serde(default)]
+    pub pinned: bool, // Angepinnte Einträge überleben das Limit
+    pub kind: ContentKind,   // Erkannte Art (URL, JSON, Code, …)
+    pub id: u64,             // Eindeutig je Eintrag, auch bei gleichem Inhalt
+}
+
+/// Eintrag wie gespeichert; ältere Verlaufsdateien haben noch kein `kind` und keine `id`
+#[derive(Deserialize)]
+struct StoredEntry {
+    content: String,
+    timestamp: u64,
+    item: ClipboardItem,
+    hash: Option<u64>,
+    #[serde(default)]
+    pinned: bool,
+    kind: Option<ContentKind>,
+    id: Option<u64>,
+}
+
+impl From<StoredEntry> for Entry {
+    /// Die Art wird nur bestimmt, wenn sie fehlt – sonst liefe die Erkennung bei jedem Laden
+    fn from(stored: StoredEntry) -> Self {
+        let kind = stored.kind.unwrap_or_else(|| match &stored.item {
+            ClipboardItem::Text(text) => kind::detect(text),
+            ClipboardItem::Image(_) => ContentKind::Image,
+        });
+        // Aus Inhalt und Zeit abgeleitet, damit alle Prozesse bis zum nächsten Speichern
+        // dieselbe ID sehen; danach steht sie in der Datei
+        let id = stored.id.unwrap_or_else(|| {
+            let content = stored.hash.unwrap_or_else(|| hash_data(&stored.content));
+            hash_data(&(content, stored.timestamp))
+        });
+        Self {
+            id,
+            content: stored.content,
+            timestamp: stored.timestamp,
+            item: stored.item,
+            hash: stored.hash,
+            pinned: stored.pinned,
+            kind,
+        }
+    }
+}
+
+impl Entry {
+    /// Einzeilige Kurzfassung für Listen: erste Textzeile bzw. Dateiname des Bildes
+    pub fn summary(&self, max_chars: usize) -> String {
+        let line = match &self.item {
+            ClipboardItem::Image(path) => path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| self.content.clone()),
+            ClipboardItem::Text(text) => text.trim().lines().next().unwrap_or_default().to_string(),
+        };
+
+        if line.chars().count() > max_chars {
+            let cut: String = line.chars().take(max_chars.saturating_sub(1)).collect();
+            format!("{cut}…")
+        } else {
+            line
+        }
+    }
+
+    /// Suche im Inhalt, Groß-/Kleinschreibung egal; leere Suche passt immer.
+    ///
+    /// Wörter der Form `kind:<art>` filtern nach erkannter Art (z. B. `kind:url`,
+    /// `kind:code:rs`); mehrere davon werden ODER-verknüpft.
+    pub fn matches(&self, query: &str) -> bool {
+        let (kinds, words): (Vec<&str>, Vec<&str>) = query
+            .split_whitespace()
+            .partition(|w| w.to_lowercase().starts_with("kind:"));
+
+        let kind_ok = kinds.is_empty() || kinds.iter().any(|k| self.kind.matches_filter(&k[5..]));
+        let keyword = words.join(" ").to_lowercase();
+
+        kind_ok && (keyword.is_empty() || self.content.to_lowercase().contains(&keyword))
+    }
+
+    /// Stabiler Schlüssel über Neuladen hinweg (Indizes verschieben sich bei neuen Einträgen);
+    /// eindeutig auch für Einträge mit gleichem Inhalt aus derselben Sekunde
+    pub fn key(&self) -> u64 {
+        self.id
+    }
+
+    /// Löscht zugehörige Dateien (Bild und Vorschaubild) – Texteinträge haben keine
+    pub fn remove_files(&self) {
+        if let ClipboardItem::Image(ref path) = self.item {
+            if path.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|ext| ext == "png" || ext == "jpg")
+            {
+                if let Err(e) = fs::remove_file(path) {
+                    warn!(path = %path.display(), "Bild konnte nicht gelöscht werden: {e}");
+                }
+            }
+            thumbnail::remove(path);
+        }
+    }
+}
+
+/// Zufällige ID für einen neuen Eintrag
+fn new_id() -> u64 {
+    uuid::Uuid::new_v4().as_u64_pair().1
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -41,8 +151,11 @@ impl History {
         let entry = Entry {
             content: text.clone(),
             timestamp: chrono::Utc::now().timestamp() as u64,
+            kind: kind::detect(&text),
             item: ClipboardItem::Text(text),
             hash: Some(hash),
+            pinned: false,
+            id: new_id(),
         };
         self.entries.insert(0, entry);
         self.cleanup();
@@ -53,10 +166,7 @@ impl History {
 
         // ✅ Prüfe, ob bereits ein Bild mit diesem Hash existiert
         if self.entries.iter().any(|e| e.hash == Some(image_hash)) {
-            println!(
-                "⚠️ Bild mit Hash {:x} bereits in History – skip.",
-                image_hash
-            );
+            debug!(hash = %format!("{image_hash:x}"), "Bild bereits im Verlauf");
             return;
         }
 
@@ -65,17 +175,57 @@ impl History {
             timestamp: chrono::Utc::now().timestamp() as u64,
             item: ClipboardItem::Image(image_path),
             hash: Some(image_hash),
+            pinned: false,
+            id: new_id(),
+            kind: ContentKind::Image,
         };
         self.entries.insert(0, entry);
         self.cleanup();
     }
 
+    /// Kürzt auf das Limit – die ältesten nicht angepinnten Einträge fliegen zuerst
     fn cleanup(&mut self) {
-        if self.entries.len() > self.limit {
-            self.entries.truncate(self.limit);
+        while self.entries.len() > self.limit {
+            match self.entries.iter().rposition(|e| !e.pinned) {
+                Some(oldest) => {
+                    self.entries.remove(oldest);
+                }
+                None => break,
+            }
         }
     }
 
+    /// Schaltet den Pin-Status eines Eintrags um und gibt den neuen Status zurück
+    pub fn toggle_pin(&mut self, index: usize) -> Option<bool> {
+        let entry = self.entries.get_mut(index)?;
+        entry.pinned = !entry.pinned;
+        Some(entry.pinned)
+    }
+
+    /// Holt einen Eintrag an die Spitze des Verlaufs (z. B. nach Auswahl in GUI oder CLI)
+    pub fn promote(&mut self, index: usize) -> Option<Entry> {
+        if index >= self.entries.len() {
+            return None;
+        }
+
+        let mut entry = self.entries.remove(index);
+
+        // Entferne andere Duplikate
+        self.entries.retain(|e| e.content != entry.content);
+
+        // Bild-Einträge: Hash ggf. nachtragen, damit der Watcher sie wiedererkennt
+        if let ClipboardItem::Image(ref path) = entry.item {
+            if entry.hash.is_none() {
+                if let Ok(data) = fs::read(path) {
+                    entry.hash = Some(hash_data(&data));
+                }
+            }
+        }
+
+        self.entries.insert(0, entry.clone());
+        Some(entry)
+    }
+
     pub fn delete_entry(&mut self, index: usize) -> bool {
         if index < self.entries.len() {
             self.entries.remove(index);
@@ -85,30 +235,123 @@ impl History {
         }
     }
 
+    /// Ersetzt den Text eines Eintrags an seiner Position (Bilder lassen sich nicht ersetzen);
+    /// andere Einträge mit demselben Inhalt werden entfernt.
+    pub fn replace_text(&mut self, index: usize, text: String) -> Option<Entry> {
+        let entry = self.entries.get_mut(index)?;
+        if let ClipboardItem::Image(_) = entry.item {
+            return None;
+        }
+
+        entry.hash = Some(hash_data(&text));
+        entry.kind = kind::detect(&text);
+        entry.content = text.clone();
+        entry.item = ClipboardItem::Text(text);
+        let updated = entry.clone();
+
+        let mut position = 0;
+        self.entries.retain(|e| {
+            position += 1;
+            position - 1 == index || e.content != updated.content
+        });
+        Some(updated)
+    }
+
+    /// Entfernt mehrere Einträge und gibt sie zurück (z. B. um Bilddateien zu löschen)
+    pub fn delete_entries(&mut self, indices: &[usize]) -> Vec<Entry> {
+        let mut indices = indices.to_vec();
+        indices.sort_unstable_by(|a, b| b.cmp(a));
+        indices.dedup();
+        indices.retain(|&i| i < self.entries.len());
+        indices
+            .into_iter()
+            .map(|i| self.entries.remove(i))
+            .collect()
+    }
+
+    /// Setzt den Pin-Status mehrerer Einträge
+    pub fn set_pinned(&mut self, indices: &[usize], pinned: bool) {
+        for &i in indices {
+            if let Some(entry) = self.entries.get_mut(i) {
+                entry.pinned = pinned;
+            }
+        }
+    }
+
+    /// Führt die Texte der Einträge in zeitlicher Reihenfolge (älteste zuerst) zu einem
+    /// neuen Eintrag zusammen; Bilder werden übersprungen.
+    pub fn merge_texts(&mut self, indices: &[usize], separator: &str) -> Option<Entry> {
+        let mut indices = indices.to_vec();
+        indices.sort_unstable_by(|a, b| b.cmp(a));
+        indices.dedup();
+
+        let texts: Vec<&str> = indices
+            .iter()
+            .filter_map(|&i| match &self.entries.get(i)?.item {
+                ClipboardItem::Text(text) => Some(text.as_str()),
+                ClipboardItem::Image(_) => None,
+            })
+            .collect();
+        if texts.is_empty() {
+            return None;
+        }
+
+        let merged = texts.join(separator);
+        self.add_text(merged);
+        self.entries.first().cloned()
+    }
+
+    /// Schreibt Einträge als Dateien in ein Verzeichnis (Texte als `.txt`, Bilder als Kopie)
+    pub fn export_entries(&self, indices: &[usize], dir: &Path) -> std::io::Result<usize> {
+        fs::create_dir_all(dir)?;
+        let mut written = 0;
+        for (n, &i) in indices.iter().enumerate() {
+            let Some(entry) = self.entries.get(i) else {
+                continue;
+            };
+            let name = format!("{:03}_{}", n + 1, entry.timestamp);
+            match &entry.item {
+                ClipboardItem::Text(text) => fs::write(dir.join(format!("{name}.txt")), text)?,
+                ClipboardItem::Image(path) => {
+                    let ext = path.extension().unwrap_or_default().to_string_lossy();
+                    fs::copy(path, dir.join(format!("{name}.{ext}")))?;
+                }
+            }
+            written += 1;
+        }
+        Ok(written)
+    }
+
     /*
     pub fn latest(&self) -> Option<&Entry> {
         self.entries.first()
     }
     */
 
-    pub fn save(&self, path: &PathBuf) -> std::io::Result<()> {
-        fs::create_dir_all(path.parent().unwrap())?;
+    pub fn save(&self, path: &Path) -> Result<()> {
+        self.write(path).map_err(Error::storage(path))
+    }
+
+    fn write(&self, path: &Path) -> io::Result<()> {
         let json = serde_json::to_string_pretty(&self)?;
-        let mut file = File::create(path)?;
-        file.write_all(json.as_bytes())?;
-        Ok(())
-    }
-
-    pub fn load(path: &PathBuf, limit: usize) -> Self {
-        if let Ok(file) = File::open(path) {
-            let reader = BufReader::new(file);
-            if let Ok(mut history) = serde_json::from_reader::<_, History>(reader) {
-                history.limit = limit;
-                history.entries.truncate(limit);
-                return history;
-            }
-        }
-        History::new(limit)
+        fswatch::write_atomic(path, json.as_bytes())
+    }
+
+    /// Fehlende oder kaputte Datei = leerer Verlauf
+    pub fn load(path: &Path, limit: usize) -> Self {
+        Self::try_load(path, limit).unwrap_or_else(|| History::new(limit))
+    }
+
+    /// `None`, wenn die Datei fehlt oder sich nicht parsen lässt – zum Neuladen, bei dem
+    /// ein Lesefehler den bekannten Verlauf nicht ersetzen darf
+    pub fn try_load(path: &Path, limit: usize) -> Option<Self> {
+        let reader = BufReader::new(File::open(path).ok()?);
+        let mut history = serde_json::from_reader::<_, History>(reader)
+            .map_err(|e| warn!(path = %path.display(), "Verlauf nicht lesbar: {e}"))
+            .ok()?;
+        history.limit = limit;
+        history.cleanup();
+        Some(history)
     }
 
     /// Löscht den kompletten Clipboard-Verlauf
@@ -123,10 +366,7 @@ impl History {
 
     /// Gibt alle Einträge zurück, die ein bestimmtes Stichwort enthalten
     pub fn search(&self, keyword: &str) -> Vec<&Entry> {
-        self.entries
-            .iter()
-            .filter(|e| e.content.to_lowercase().contains(&keyword.to_lowercase()))
-            .collect()
+        self.entries.iter().filter(|e| e.matches(keyword)).collect()
     }
 }
 
@@ -176,6 +416,154 @@ mod tests {
         assert_eq!(res[0].content, "hello world");
     }
 
+    #[test]
+    fn summary_uses_first_line_and_truncates() {
+        let mut hist = History::new(5);
+        hist.add_text("  erste Zeile\nzweite Zeile".into());
+        assert_eq!(hist.entries[0].summary(40), "erste Zeile");
+        assert_eq!(hist.entries[0].summary(5), "erst…");
+
+        hist.add_image(PathBuf::from("/tmp/bilder/clip_1.png"), 7);
+        assert_eq!(hist.entries[0].summary(40), "clip_1.png");
+    }
+
+    #[test]
+    fn promote_moves_entry_to_front() {
+        let mut hist = History::new(5);
+        hist.add_text("a".into());
+        hist.add_text("b".into());
+        hist.add_text("c".into());
+
+        let promoted = hist.promote(2).unwrap();
+        assert_eq!(promoted.content, "a");
+        assert_eq!(hist.entries[0].content, "a");
+        assert_eq!(hist.entries.len(), 3);
+        assert!(hist.promote(3).is_none());
+    }
+
+    #[test]
+    fn pinned_entries_survive_limit() {
+        let mut hist = History::new(2);
+        hist.add_text("keep".into());
+        assert_eq!(hist.toggle_pin(0), Some(true));
+        hist.add_text("a".into());
+        hist.add_text("b".into());
+        hist.add_text("c".into());
+
+        assert_eq!(hist.entries.len(), 2);
+        assert_eq!(hist.entries[0].content, "c");
+        assert_eq!(hist.entries[1].content, "keep");
+        assert_eq!(hist.toggle_pin(9), None);
+    }
+
+    #[test]
+    fn search_filters_by_kind() {
+        let mut hist = History::new(5);
+        hist.add_text("https://example.org".into());
+        hist.add_text("example text".into());
+        hist.add_image(PathBuf::from("/tmp/example.png"), 1);
+
+        assert_eq!(hist.entries[2].kind, ContentKind::Url);
+        assert_eq!(hist.search("example").len(), 3);
+        assert_eq!(hist.search("kind:url example").len(), 1);
+        assert_eq!(hist.search("kind:url kind:image").len(), 2);
+        assert!(hist.search("kind:json").is_empty());
+    }
+
+    #[test]
+    fn keys_differ_for_equal_content() {
+        let mut hist = History::new(5);
+        hist.add_text("gleich".into());
+        hist.add_text("anders".into());
+        hist.add_text("gleich".into());
+        // Gleiche Sekunde, auch wenn der Test an einer Sekundengrenze läuft
+        hist.entries[2].timestamp = hist.entries[0].timestamp;
+
+        assert_eq!(hist.entries[0].content, hist.entries[2].content);
+        assert_ne!(hist.entries[0].key(), hist.entries[2].key());
+        let reloaded: History = serde_json::from_str(&hist.export_json().unwrap()).unwrap();
+        assert_eq!(reloaded.entries[2].key(), hist.entries[2].key());
+    }
+
+    #[test]
+    fn missing_ids_are_derived_the_same_way_on_every_load() {
+        let json = r#"{"limit": 5, "entries": [
+            {"content": "a", "timestamp": 1, "item": {"Text": "a"}, "hash": 1},
+            {"content": "a", "timestamp": 2, "item": {"Text": "a"}, "hash": 1}
+        ]}"#;
+        let first: History = serde_json::from_str(json).unwrap();
+        let second: History = serde_json::from_str(json).unwrap();
+        assert_eq!(first.entries[0].key(), second.entries[0].key());
+        assert_ne!(first.entries[0].key(), first.entries[1].key());
+    }
+
+    #[test]
+    fn stored_kind_is_kept_and_missing_kind_detected() {
+        let json = r#"{"limit": 5, "entries": [
+            {"content": "https://a.org", "timestamp": 1, "item": {"Text": "https://a.org"}, "hash": 1},
+            {"content": "https://b.org", "timestamp": 2, "item": {"Text": "https://b.org"}, "hash": 2, "kind": "text"}
+        ]}"#;
+        let hist: History = serde_json::from_str(json).unwrap();
+        assert_eq!(hist.entries[0].kind, ContentKind::Url);
+        assert_eq!(hist.entries[1].kind, ContentKind::Text);
+    }
+
+    #[test]
+    fn merge_and_bulk_actions() {
+        let mut hist = History::new(10);
+        hist.add_text("eins".into());
+        hist.add_image(PathBuf::from("/tmp/hyprclip_bulk.png"), 3);
+        hist.add_text("zwei".into());
+
+        let merged = hist.merge_texts(&[0, 1, 2], " | ").unwrap();
+        assert_eq!(merged.content, "eins | zwei");
+        assert_eq!(hist.entries.len(), 4);
+        assert!(hist.merge_texts(&[2], "\n").is_none());
+
+        hist.set_pinned(&[0, 3], true);
+        assert!(hist.entries[0].pinned && hist.entries[3].pinned);
+
+        let removed = hist.delete_entries(&[3, 0, 3]);
+        assert_eq!(removed.len(), 2);
+        assert_eq!(hist.entries.len(), 2);
+        assert_eq!(hist.entries[0].content, "zwei");
+    }
+
+    #[test]
+    fn replace_text_updates_in_place() {
+        let mut hist = History::new(5);
+        hist.add_text("https://example.org".into());
+        hist.add_text("tippfehler".into());
+        hist.add_text("oben".into());
+
+        let updated = hist.replace_text(1, "https://example.org".into()).unwrap();
+        assert_eq!(updated.kind, ContentKind::Url);
+        // Duplikat weiter unten ist weg, Position bleibt erhalten
+        assert_eq!(hist.entries.len(), 2);
+        assert_eq!(hist.entries[1].content, "https://example.org");
+
+        hist.add_image(PathBuf::from("/tmp/hyprclip_edit.png"), 9);
+        assert!(hist.replace_text(0, "text".into()).is_none());
+    }
+
+    #[test]
+    fn export_entries_writes_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut hist = History::new(5);
+        hist.add_text("alpha".into());
+        hist.add_text("beta".into());
+
+        let target = dir.path().join("export");
+        assert_eq!(hist.export_entries(&[1, 0], &target).unwrap(), 2);
+        let mut files: Vec<_> = fs::read_dir(&target)
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .collect();
+        files.sort();
+        assert_eq!(fs::read_to_string(&files[0]).unwrap(), "alpha");
+        assert_eq!(fs::read_to_string(&files[1]).unwrap(), "beta");
+    }
+
     #[test]
     fn export_json_valid() {
         let mut hist = History::new(5);
@@ -0,0 +1,344 @@
+use super::{run_command, HookEvent, HookMatch, Matcher};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tracing::warn;
+
+/// Filter, der einen Eintrag vor dem Speichern umschreiben oder verwerfen kann
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FilterHook {
+    /// Name für Logausgaben
+    #[serde(default)]
+    pub name: String,
+    /// Externer Befehl: stdout ersetzt den Inhalt, Exit-Code ≠ 0 verwirft ihn
+    #[serde(default)]
+    pub command: Option<String>,
+    /// Eingebauter Filter (nur für Texte)
+    #[serde(default)]
+    pub builtin: Option<BuiltinFilter>,
+    #[serde(default)]
+    pub when: HookMatch,
+    /// Überschreibt `hooks.filter_timeout_ms`
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    /// Verhalten, wenn der Befehl nicht startet oder zu lange braucht
+    #[serde(default)]
+    pub on_error: FailMode,
+}
+
+impl FilterHook {
+    /// Kompilierte Bedingungen; Fehler bei ungültigem Ausdruck oder ohne bzw. mit doppelter Aktion
+    pub fn matcher(&self) -> Result<Matcher, String> {
+        if self.command.is_some() == self.builtin.is_some() {
+            return Err("genau eins von `command` oder `builtin` angeben".to_string());
+        }
+        Matcher::new(&self.when).map_err(|e| e.to_string())
+    }
+}
+
+/// Eingebaute Filter in Rust
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BuiltinFilter {
+    /// Entfernt Tracking-Parameter (`utm_*`, `fbclid`, …) aus URLs
+    StripTrackingParams,
+    /// Entfernt Leerraum am Zeilenende und am Textende
+    TrimTrailingWhitespace,
+    /// Wandelt `\r\n` und `\r` in `\n` um
+    NormalizeLineEndings,
+}
+
+/// Fehlerverhalten eines Filters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FailMode {
+    /// Inhalt unverändert übernehmen
+    #[default]
+    Open,
+    /// Inhalt verwerfen
+    Closed,
+}
+
+/// Ergebnis der Filterkette
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Filtered<T> {
+    Store(T),
+    /// Nicht speichern; enthält den Namen des Filters
+    Veto(String),
+}
+
+/// Alle Filter in der Reihenfolge der Konfiguration; jeder sieht das Ergebnis des vorherigen
+pub struct FilterChain {
+    filters: Vec<(FilterHook, Matcher)>,
+    default_timeout: Duration,
+}
+
+impl FilterChain {
+    /// Erwartet eine geprüfte Konfiguration (siehe [`super::HooksConfig::problems`]);
+    /// ungültige Filter werden nur zur Sicherheit mit Warnung übersprungen
+    pub fn new(filters: &[FilterHook], timeout_ms: u64) -> Self {
+        let filters = filters
+            .iter()
+            .filter_map(|f| match f.matcher() {
+                Ok(matcher) => Some((f.clone(), matcher)),
+                Err(e) => {
+                    warn!(filter = %f.name, "Filter deaktiviert: {e}");
+                    None
+                }
+            })
+            .collect();
+
+        Self {
+            filters,
+            default_timeout: Duration::from_millis(timeout_ms),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.filters.is_empty()
+    }
+
+    pub async fn filter_text(&self, text: String, source_app: Option<String>) -> Filtered<String> {
+        let mut text = text;
+        for (filter, matcher) in &self.filters {
+            let event = HookEvent::for_text(&text, source_app.clone());
+            if !matcher.matches(&event) {
+                continue;
+            }
+
+            let result = match (filter.builtin, &filter.command) {
+                (Some(builtin), _) => Ok(Some(builtin.apply(&text).into_bytes())),
+                (None, Some(command)) => self.run(filter, command, &event, text.as_bytes()).await,
+                (None, None) => continue,
+            };
+            match result {
+                // Leere Ausgabe (z. B. `grep -q` als reine Prüfung) lässt den Text unverändert
+                Ok(Some(output)) if !output.is_empty() => {
+                    text = String::from_utf8_lossy(&output).into_owned()
+                }
+                Ok(Some(_)) | Err(()) => {}
+                Ok(None) => return Filtered::Veto(filter.name.clone()),
+            }
+        }
+        Filtered::Store(text)
+    }
+
+    /// Bilder durchlaufen nur Befehls-Filter
+    pub async fn filter_image(
+        &self,
+        data: Vec<u8>,
+        source_app: Option<String>,
+    ) -> Filtered<Vec<u8>> {
+        let mut data = data;
+        let event = HookEvent::for_image(source_app);
+        for (filter, matcher) in &self.filters {
+            let Some(command) = &filter.command else {
+                continue;
+            };
+            if !matcher.matches(&event) {
+                continue;
+            }
+            match self.run(filter, command, &event, &data).await {
+                Ok(Some(output)) if !output.is_empty() => data = output,
+                Ok(Some(_)) | Err(()) => {}
+                Ok(None) => return Filtered::Veto(filter.name.clone()),
+            }
+        }
+        Filtered::Store(data)
+    }
+
+    /// `Ok(Some)` = neue Ausgabe, `Ok(None)` = Veto, `Err` = Fehler ohne Veto (fail-open)
+    async fn run(
+        &self,
+        filter: &FilterHook,
+        command: &str,
+        event: &HookEvent,
+        input: &[u8],
+    ) -> Result<Option<Vec<u8>>, ()> {
+        let timeout = filter
+            .timeout_ms
+            .map(Duration::from_millis)
+            .unwrap_or(self.default_timeout);
+
+        let error = match run_command(command, event.env(&filter.name), input, timeout).await {
+            Ok(output) if output.status.success() => return Ok(Some(output.stdout)),
+            // 126/127: von `sh` nicht ausführbar bzw. nicht gefunden, ohne Code: per Signal
+            // beendet – ein Fehler des Filters, kein bewusstes Veto
+            Ok(output) => match output.status.code() {
+                Some(126 | 127) | None => format!(
+                    "beendet mit {}: {}",
+                    output.status,
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ),
+                Some(_) => return Ok(None),
+            },
+            Err(e) => e,
+        };
+        warn!(filter = %filter.name, "Filter fehlgeschlagen: {error}");
+        match filter.on_error {
+            FailMode::Open => Err(()),
+            FailMode::Closed => Ok(None),
+        }
+    }
+}
+
+impl BuiltinFilter {
+    pub fn apply(self, text: &str) -> String {
+        match self {
+            BuiltinFilter::StripTrackingParams => text
+                .split_inclusive(char::is_whitespace)
+                .map(|token| {
+                    let trimmed = token.trim_end();
+                    let url = strip_tracking_params(trimmed);
+                    format!("{url}{}", &token[trimmed.len()..])
+                })
+                .collect(),
+            BuiltinFilter::TrimTrailingWhitespace => text
+                .lines()
+                .map(str::trim_end)
+                .collect::<Vec<_>>()
+                .join("\n")
+                .trim_end()
+                .to_string(),
+            BuiltinFilter::NormalizeLineEndings => text.replace("\r\n", "\n").replace('\r', "\n"),
+        }
+    }
+}
+
+/// Parameter, die nur der Nachverfolgung dienen
+const TRACKING_PARAMS: &[&str] = &[
+    "fbclid", "gclid", "dclid", "gbraid", "wbraid", "msclkid", "yclid", "mc_cid", "mc_eid",
+    "igshid", "igsh", "si", "ref_src", "ref_url", "_hsenc", "_hsmi", "mkt_tok", "spm",
+];
+
+fn is_tracking_param(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    key.starts_with("utm_") || TRACKING_PARAMS.contains(&key.as_str())
+}
+
+fn strip_tracking_params(url: &str) -> String {
+    let lower = url.to_ascii_lowercase();
+    if !(lower.starts_with("http://") || lower.starts_with("https://")) {
+        return url.to_string();
+    }
+
+    let (rest, fragment) = match url.split_once('#') {
+        Some((rest, fragment)) => (rest, Some(fragment)),
+        None => (url, None),
+    };
+    let Some((base, query)) = rest.split_once('?') else {
+        return url.to_string();
+    };
+
+    let kept: Vec<&str> = query
+        .split('&')
+        .filter(|pair| {
+            let key = pair.split('=').next().unwrap_or_default();
+            !pair.is_empty() && !is_tracking_param(key)
+        })
+        .collect();
+
+    let mut out = base.to_string();
+    if !kept.is_empty() {
+        out.push('?');
+        out.push_str(&kept.join("&"));
+    }
+    if let Some(fragment) = fragment {
+        out.push('#');
+        out.push_str(fragment);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(command: Option<&str>, builtin: Option<BuiltinFilter>) -> FilterHook {
+        FilterHook {
+            name: "test".into(),
+            command: command.map(str::to_string),
+            builtin,
+            when: HookMatch::default(),
+            timeout_ms: Some(200),
+            on_error: FailMode::Open,
+        }
+    }
+
+    #[test]
+    fn builtins_clean_text() {
+        assert_eq!(
+            BuiltinFilter::StripTrackingParams.apply(
+                "siehe https://example.org/a?id=3&utm_source=x&fbclid=y#top und https://x.io/?utm_medium=z"
+            ),
+            "siehe https://example.org/a?id=3#top und https://x.io/"
+        );
+        assert_eq!(
+            BuiltinFilter::TrimTrailingWhitespace.apply("a  \nb\t\n\n"),
+            "a\nb"
+        );
+        assert_eq!(
+            BuiltinFilter::NormalizeLineEndings.apply("a\r\nb\rc"),
+            "a\nb\nc"
+        );
+    }
+
+    #[tokio::test]
+    async fn commands_rewrite_or_veto() {
+        let chain = FilterChain::new(
+            &[
+                filter(None, Some(BuiltinFilter::TrimTrailingWhitespace)),
+                filter(Some("tr a-z A-Z"), None),
+            ],
+            1000,
+        );
+        assert_eq!(
+            chain.filter_text("hallo   ".into(), None).await,
+            Filtered::Store("HALLO".into())
+        );
+
+        let veto = FilterChain::new(&[filter(Some("grep -qv geheim"), None)], 1000);
+        assert_eq!(
+            veto.filter_text("streng geheim".into(), None).await,
+            Filtered::Veto("test".into())
+        );
+        assert!(matches!(
+            veto.filter_text("öffentlich".into(), None).await,
+            Filtered::Store(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn missing_commands_respect_fail_mode() {
+        let mut typo = filter(Some("hyprclip-gibt-es-nicht"), None);
+        let open = FilterChain::new(std::slice::from_ref(&typo), 1000);
+        assert_eq!(
+            open.filter_text("x".into(), None).await,
+            Filtered::Store("x".into())
+        );
+
+        typo.on_error = FailMode::Closed;
+        let closed = FilterChain::new(&[typo], 1000);
+        assert!(matches!(
+            closed.filter_text("x".into(), None).await,
+            Filtered::Veto(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn timeouts_respect_fail_mode() {
+        let mut slow = filter(Some("sleep 5"), None);
+        let open = FilterChain::new(std::slice::from_ref(&slow), 1000);
+        assert_eq!(
+            open.filter_text("x".into(), None).await,
+            Filtered::Store("x".into())
+        );
+
+        slow.on_error = FailMode::Closed;
+        let closed = FilterChain::new(&[slow], 1000);
+        assert!(matches!(
+            closed.filter_text("x".into(), None).await,
+            Filtered::Veto(_)
+        ));
+    }
+}
@@ -0,0 +1,463 @@
+mod filter;
+
+pub use filter::{FilterChain, FilterHook, Filtered};
+
+use crate::{
+    history::{ClipboardItem, Entry},
+    hyprland,
+    kind::{self, ContentKind},
+};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::{path::PathBuf, process::Stdio, sync::Arc, time::Duration};
+use tokio::{io::AsyncWriteExt, process::Command, sync::Semaphore};
+use tracing::warn;
+
+/// Abschnitt `[hooks]` der Konfiguration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HooksConfig {
+    /// Wie viele Hooks höchstens gleichzeitig laufen
+    pub max_concurrent: usize,
+    /// Standard-Zeitlimit je Hook
+    pub timeout_ms: u64,
+    /// Befehle, die bei neuen Einträgen im Hintergrund laufen
+    pub on_new: Vec<Hook>,
+    /// Zeitlimit je Filter – der Watcher wartet auf sie
+    pub filter_timeout_ms: u64,
+    /// Filter, die vor dem Speichern umschreiben oder verwerfen
+    pub filters: Vec<FilterHook>,
+}
+
+impl HooksConfig {
+    /// Hooks und Filter, die nicht laufen könnten – `Config::load` schlägt damit fehl
+    pub fn problems(&self) -> Vec<String> {
+        let hooks = self.on_new.iter().enumerate().filter_map(|(i, hook)| {
+            let e = Matcher::new(&hook.when).err()?;
+            Some(format!("hooks.on_new[{i}]{}: {e}", label(&hook.name)))
+        });
+        let filters = self.filters.iter().enumerate().filter_map(|(i, filter)| {
+            let e = filter.matcher().err()?;
+            Some(format!("hooks.filters[{i}]{}: {e}", label(&filter.name)))
+        });
+        hooks.chain(filters).collect()
+    }
+}
+
+// Name für Meldungen, sofern vergeben
+fn label(name: &str) -> String {
+    if name.is_empty() {
+        String::new()
+    } else {
+        format!(" („{name}“)")
+    }
+}
+
+impl Default for HooksConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent: 4,
+            timeout_ms: 10_000,
+            on_new: Vec::new(),
+            filter_timeout_ms: 2_000,
+            filters: Vec::new(),
+        }
+    }
+}
+
+/// Ein Befehl (per `sh -c`) mit Bedingungen; der Inhalt kommt über stdin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Hook {
+    /// Name für Logausgaben
+    #[serde(default)]
+    pub name: String,
+    pub command: String,
+    #[serde(default)]
+    pub when: HookMatch,
+    /// Überschreibt `hooks.timeout_ms`
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+}
+
+/// Bedingungen eines Hooks: alle angegebenen müssen passen, Listen sind ODER-verknüpft
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HookMatch {
+    /// Inhaltsarten wie bei der Suche, z. B. `url` oder `code:rs`
+    pub kind: Vec<String>,
+    /// MIME-Typen, `image/*` als Platzhalter erlaubt
+    pub mime: Vec<String>,
+    /// Regulärer Ausdruck auf den Text (Bilder passen nie)
+    pub regex: Option<String>,
+    /// Fensterklasse der Anwendung, aus der kopiert wurde (Hyprland)
+    pub source_app: Vec<String>,
+}
+
+/// Metadaten eines neuen Eintrags für Bedingungen und Umgebungsvariablen
+#[derive(Debug, Clone)]
+pub struct HookEvent {
+    pub kind: ContentKind,
+    pub mime: String,
+    pub text: Option<String>,
+    pub path: Option<PathBuf>,
+    pub source_app: Option<String>,
+    pub timestamp: u64,
+    pub hash: Option<u64>,
+}
+
+impl HookEvent {
+    pub fn from_entry(entry: &Entry, source_app: Option<String>) -> Self {
+        let (mime, text, path) = match &entry.item {
+            ClipboardItem::Text(text) => ("text/plain", Some(text.clone()), None),
+            ClipboardItem::Image(path) => ("image/png", None, Some(path.clone())),
+        };
+        Self {
+            kind: entry.kind.clone(),
+            mime: mime.to_string(),
+            text,
+            path,
+            source_app,
+            timestamp: entry.timestamp,
+            hash: entry.hash,
+        }
+    }
+
+    /// Für Filter: der Text ist noch nicht gespeichert
+    pub fn for_text(text: &str, source_app: Option<String>) -> Self {
+        Self {
+            kind: kind::detect(text),
+            mime: "text/plain".to_string(),
+            text: Some(text.to_string()),
+            path: None,
+            source_app,
+            timestamp: chrono::Utc::now().timestamp() as u64,
+            hash: None,
+        }
+    }
+
+    /// Für Filter: das Bild liegt noch nicht als Datei vor
+    pub fn for_image(source_app: Option<String>) -> Self {
+        Self {
+            kind: ContentKind::Image,
+            mime: "image/png".to_string(),
+            text: None,
+            path: None,
+            source_app,
+            timestamp: chrono::Utc::now().timestamp() as u64,
+            hash: None,
+        }
+    }
+
+    /// Inhalt für stdin: Text bzw. die Bytes der Bilddatei
+    async fn content(&self) -> Vec<u8> {
+        match (&self.text, &self.path) {
+            (Some(text), _) => text.as_bytes().to_vec(),
+            (None, Some(path)) => tokio::fs::read(path).await.unwrap_or_default(),
+            (None, None) => Vec::new(),
+        }
+    }
+
+    /// Umgebung für den Hook-Prozess, siehe [`ENV_VARS`]
+    fn env(&self, hook: &str) -> Vec<(&'static str, String)> {
+        let mut env = vec![
+            ("HYPRCLIP_HOOK", hook.to_string()),
+            ("HYPRCLIP_KIND", self.kind.to_string()),
+            ("HYPRCLIP_MIME", self.mime.clone()),
+            ("HYPRCLIP_TIMESTAMP", self.timestamp.to_string()),
+            (
+                "HYPRCLIP_SOURCE_APP",
+                self.source_app.clone().unwrap_or_default(),
+            ),
+        ];
+        if let Some(hash) = self.hash {
+            env.push(("HYPRCLIP_HASH", format!("{hash:x}")));
+        }
+        if let Some(path) = &self.path {
+            env.push(("HYPRCLIP_PATH", path.display().to_string()));
+        }
+        if let Some(text) = &self.text {
+            env.push(("HYPRCLIP_SIZE", text.len().to_string()));
+        }
+        env
+    }
+}
+
+/// Zeitlimit für `hyprctl activewindow`; der Watcher wartet darauf
+const SOURCE_APP_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Fensterklasse des aktiven Fensters – meist die Anwendung, aus der gerade kopiert wurde.
+/// Läuft asynchron mit Zeitlimit, damit ein hängendes `hyprctl` den Watcher nicht blockiert.
+pub async fn source_app() -> Option<String> {
+    if !hyprland::is_running() {
+        return None;
+    }
+    let run = Command::new("hyprctl")
+        .args(["activewindow", "-j"])
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .output();
+    let output = match tokio::time::timeout(SOURCE_APP_TIMEOUT, run).await {
+        Ok(result) => result.ok()?,
+        Err(_) => {
+            warn!("hyprctl antwortet nicht, Quellanwendung unbekannt");
+            return None;
+        }
+    };
+    if !output.status.success() {
+        return None;
+    }
+    serde_json::from_slice::<hyprland::ActiveWindow>(&output.stdout)
+        .ok()
+        .map(|w| w.class)
+        .filter(|c| !c.is_empty())
+}
+
+/// Vorkompilierte Bedingungen
+#[derive(Debug, Clone)]
+pub struct Matcher {
+    when: HookMatch,
+    regex: Option<Regex>,
+}
+
+impl Matcher {
+    pub fn new(when: &HookMatch) -> Result<Self, regex::Error> {
+        let regex = when.regex.as_deref().map(Regex::new).transpose()?;
+        Ok(Self {
+            when: when.clone(),
+            regex,
+        })
+    }
+
+    pub fn matches(&self, event: &HookEvent) -> bool {
+        let when = &self.when;
+        let kind_ok =
+            when.kind.is_empty() || when.kind.iter().any(|k| event.kind.matches_filter(k));
+        let mime_ok = when.mime.is_empty()
+            || when.mime.iter().any(|m| match m.strip_suffix("/*") {
+                Some(prefix) => event.mime.split('/').next() == Some(prefix),
+                None => *m == event.mime,
+            });
+        let regex_ok = self
+            .regex
+            .as_ref()
+            .is_none_or(|re| event.text.as_deref().is_some_and(|text| re.is_match(text)));
+        let app_ok = when.source_app.is_empty()
+            || event
+                .source_app
+                .as_deref()
+                .is_some_and(|app| when.source_app.iter().any(|a| a.eq_ignore_ascii_case(app)));
+
+        kind_ok && mime_ok && regex_ok && app_ok
+    }
+}
+
+/// Variablen, die Hooks und Filter bekommen; ruft ein Hook `hyprclip` auf, meldet das
+/// Laden der Konfiguration sie nicht als unbekannt
+pub const ENV_VARS: &[&str] = &[
+    "HYPRCLIP_HOOK",
+    "HYPRCLIP_KIND",
+    "HYPRCLIP_MIME",
+    "HYPRCLIP_TIMESTAMP",
+    "HYPRCLIP_SOURCE_APP",
+    "HYPRCLIP_HASH",
+    "HYPRCLIP_PATH",
+    "HYPRCLIP_SIZE",
+];
+
+/// Führt `on_new`-Hooks nebenläufig aus, ohne den Watcher aufzuhalten
+pub struct HookRunner {
+    hooks: Vec<(Hook, Matcher)>,
+    default_timeout: Duration,
+    permits: Arc<Semaphore>,
+}
+
+impl HookRunner {
+    /// Erwartet eine geprüfte Konfiguration (siehe [`HooksConfig::problems`]);
+    /// ungültige Hooks werden nur zur Sicherheit mit Warnung übersprungen
+    pub fn new(cfg: &HooksConfig) -> Self {
+        let hooks = cfg
+            .on_new
+            .iter()
+            .filter_map(|hook| match Matcher::new(&hook.when) {
+                Ok(matcher) => Some((hook.clone(), matcher)),
+                Err(e) => {
+                    warn!(hook = %hook.name, "Hook deaktiviert: {e}");
+                    None
+                }
+            })
+            .collect();
+
+        Self {
+            hooks,
+            default_timeout: Duration::from_millis(cfg.timeout_ms),
+            permits: Arc::new(Semaphore::new(cfg.max_concurrent.max(1))),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+
+    /// Startet alle passenden Hooks als Tokio-Tasks und kehrt sofort zurück
+    pub fn dispatch(&self, event: HookEvent) -> Vec<tokio::task::JoinHandle<()>> {
+        let event = Arc::new(event);
+        self.hooks
+            .iter()
+            .filter(|(_, matcher)| matcher.matches(&event))
+            .map(|(hook, _)| {
+                let hook = hook.clone();
+                let event = Arc::clone(&event);
+                let permits = Arc::clone(&self.permits);
+                let timeout = hook
+                    .timeout_ms
+                    .map(Duration::from_millis)
+                    .unwrap_or(self.default_timeout);
+
+                tokio::spawn(async move {
+                    // Wartet, bis ein Platz frei ist – der Watcher selbst läuft weiter
+                    let Ok(_permit) = permits.acquire_owned().await else {
+                        return;
+                    };
+                    if let Err(e) = run_hook(&hook, &event, timeout).await {
+                        warn!(hook = %hook.name, "Hook fehlgeschlagen: {e}");
+                    }
+                })
+            })
+            .collect()
+    }
+}
+
+async fn run_hook(hook: &Hook, event: &HookEvent, timeout: Duration) -> Result<(), String> {
+    let content = event.content().await;
+    let output = run_command(&hook.command, event.env(&hook.name), &content, timeout).await?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "beendet mit {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+/// Startet `sh -c <command>`, schreibt `input` nach stdin und sammelt die Ausgabe ein.
+/// Ein Fehler bedeutet: nicht gestartet oder Zeitlimit überschritten.
+pub(crate) async fn run_command(
+    command: &str,
+    env: Vec<(&'static str, String)>,
+    input: &[u8],
+    timeout: Duration,
+) -> Result<std::process::Output, String> {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .envs(env)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        // Bei Zeitüberschreitung wird der Prozess beim Drop beendet
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Start fehlgeschlagen: {e}"))?;
+
+    let mut stdin = child.stdin.take().ok_or("stdin nicht verfügbar")?;
+    let write = async move {
+        // Befehle dürfen stdin ignorieren
+        let _ = stdin.write_all(input).await;
+    };
+    // Gleichzeitig schreiben und lesen, damit volle Pipes nicht blockieren
+    let run = async move { tokio::join!(write, child.wait_with_output()).1 };
+
+    match tokio::time::timeout(timeout, run).await {
+        Err(_) => Err(format!(
+            "Zeitlimit von {} ms überschritten",
+            timeout.as_millis()
+        )),
+        Ok(result) => result.map_err(|e| e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::History;
+
+    fn text_event(text: &str) -> HookEvent {
+        let mut hist = History::new(5);
+        hist.add_text(text.to_string());
+        HookEvent::from_entry(&hist.entries[0], Some("firefox".into()))
+    }
+
+    #[test]
+    fn matcher_combines_conditions() {
+        let url = text_event("https://github.com/hyprwm");
+        let when = HookMatch {
+            kind: vec!["url".into()],
+            regex: Some("github\\.com".into()),
+            source_app: vec!["Firefox".into()],
+            ..Default::default()
+        };
+        assert!(Matcher::new(&when).unwrap().matches(&url));
+        assert!(!Matcher::new(&when)
+            .unwrap()
+            .matches(&text_event("https://example.org")));
+
+        let images = HookMatch {
+            mime: vec!["image/*".into()],
+            ..Default::default()
+        };
+        assert!(!Matcher::new(&images).unwrap().matches(&url));
+        assert!(Matcher::new(&HookMatch {
+            regex: Some("(".into()),
+            ..Default::default()
+        })
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn hooks_receive_content_and_metadata() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("out.txt");
+        let cfg = HooksConfig {
+            on_new: vec![Hook {
+                name: "save".into(),
+                command: format!(
+                    "cat > {0} && echo \" $HYPRCLIP_KIND $HYPRCLIP_SOURCE_APP\" >> {0}",
+                    out.display()
+                ),
+                when: HookMatch::default(),
+                timeout_ms: None,
+            }],
+            ..Default::default()
+        };
+
+        for handle in HookRunner::new(&cfg).dispatch(text_event("https://example.org")) {
+            handle.await.unwrap();
+        }
+        assert_eq!(
+            std::fs::read_to_string(&out).unwrap(),
+            "https://example.org url firefox\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn slow_hooks_are_killed_after_timeout() {
+        let cfg = HooksConfig {
+            on_new: vec![Hook {
+                name: "slow".into(),
+                command: "sleep 5".into(),
+                when: HookMatch::default(),
+                timeout_ms: Some(100),
+            }],
+            ..Default::default()
+        };
+
+        let started = std::time::Instant::now();
+        for handle in HookRunner::new(&cfg).dispatch(text_event("x")) {
+            handle.await.unwrap();
+        }
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+}
@@ -0,0 +1,98 @@
+use serde::Deserialize;
+use std::{
+    io,
+    process::{Command, Stdio},
+};
+
+/// Monitor-Informationen aus `hyprctl monitors -j`
+#[derive(Debug, Clone, Deserialize)]
+pub struct Monitor {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub scale: f32,
+    pub focused: bool,
+}
+
+impl Monitor {
+    /// Logische Größe (Pixel / Skalierung), wie sie Hyprland für Fensterpositionen nutzt
+    pub fn logical_size(&self) -> (f32, f32) {
+        let scale = if self.scale > 0.0 { self.scale } else { 1.0 };
+        (self.width as f32 / scale, self.height as f32 / scale)
+    }
+}
+
+/// Das aktuell fokussierte Fenster aus `hyprctl activewindow -j`
+#[derive(Debug, Clone, Deserialize)]
+pub struct ActiveWindow {
+    pub class: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CursorPos {
+    x: f32,
+    y: f32,
+}
+
+/// Prüft, ob wir innerhalb einer Hyprland-Session laufen
+pub fn is_running() -> bool {
+    std::env::var_os("HYPRLAND_INSTANCE_SIGNATURE").is_some()
+}
+
+/// Aktuelle Mausposition in globalen, logischen Koordinaten
+pub fn cursor_pos() -> Option<(f32, f32)> {
+    let pos: CursorPos = hyprctl_json(&["cursorpos"])?;
+    Some((pos.x, pos.y))
+}
+
+/// Der aktuell fokussierte Monitor
+pub fn focused_monitor() -> Option<Monitor> {
+    let monitors: Vec<Monitor> = hyprctl_json(&["monitors"])?;
+    monitors.into_iter().find(|m| m.focused)
+}
+
+/// Das fokussierte Fenster (keins, wenn gerade nur der Desktop sichtbar ist)
+pub fn active_window() -> Option<ActiveWindow> {
+    hyprctl_json(&["activewindow"])
+}
+
+/// Führt mehrere Dispatcher in einem `hyprctl --batch` Aufruf aus
+pub fn dispatch_batch(commands: &[String]) -> io::Result<()> {
+    let batch = commands
+        .iter()
+        .map(|c| format!("dispatch {c}"))
+        .collect::<Vec<_>>()
+        .join(" ; ");
+
+    let status = Command::new("hyprctl")
+        .args(["--batch", &batch])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!("hyprctl beendet mit {status}")))
+    }
+}
+
+fn hyprctl_json<T: for<'de> Deserialize<'de>>(args: &[&str]) -> Option<T> {
+    if !is_running() {
+        return None;
+    }
+
+    let output = Command::new("hyprctl")
+        .args(args)
+        .arg("-j")
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    serde_json::from_slice(&output.stdout).ok()
+}
@@ -0,0 +1,422 @@
+use egui::{Key, Modifiers};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    time::{Duration, Instant},
+};
+
+/// Aktionen, die in der GUI an Tasten gebunden werden können
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Next,
+    Prev,
+    PageDown,
+    PageUp,
+    Top,
+    Bottom,
+    Select,
+    SelectAndPaste,
+    Delete,
+    Pin,
+    /// Eintrag für Sammelaktionen markieren bzw. Markierung aufheben
+    ToggleMark,
+    /// Markierung nach unten bzw. oben erweitern
+    MarkNext,
+    MarkPrev,
+    /// Markierte Texte zu einem neuen Eintrag zusammenführen
+    Merge,
+    /// Texteintrag im Editor öffnen
+    Edit,
+    /// Umwandlung (Groß/Klein, Base64, JSON, …) auswählen
+    Transform,
+    /// Zwischen Verlauf und Snippets wechseln
+    SwitchTab,
+    Preview,
+    SearchFocus,
+    Quit,
+}
+
+/// Eine einzelne Taste inklusive Modifikatoren, z. B. `Ctrl+d` oder `G`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyChord {
+    pub key: Key,
+    pub ctrl: bool,
+    pub alt: bool,
+    /// `None` = Shift egal (Sonderzeichen liegen je nach Layout auf Shift)
+    pub shift: Option<bool>,
+}
+
+impl KeyChord {
+    /// Prüft, ob ein Tastendruck aus egui zu dieser Kombination passt
+    pub fn matches(&self, key: Key, modifiers: Modifiers) -> bool {
+        self.key == key
+            && self.ctrl == modifiers.ctrl
+            && self.alt == modifiers.alt
+            && self.shift.is_none_or(|shift| shift == modifiers.shift)
+    }
+
+    /// Tasten, die ein fokussiertes Textfeld selbst verarbeitet (Zeichen, Cursor, Löschen)
+    pub fn is_handled_by_text_field(key: Key, modifiers: Modifiers) -> bool {
+        if modifiers.ctrl || modifiers.alt {
+            return false;
+        }
+        !matches!(
+            key,
+            Key::ArrowDown
+                | Key::ArrowUp
+                | Key::Escape
+                | Key::Enter
+                | Key::Tab
+                | Key::PageDown
+                | Key::PageUp
+        )
+    }
+
+    fn parse(token: &str) -> Result<Self, String> {
+        let mut parts: Vec<&str> = token.split('+').collect();
+        // "Ctrl++" bzw. "+" meint die Plus-Taste selbst
+        if token.ends_with("++") || token == "+" {
+            parts.retain(|p| !p.is_empty());
+            parts.push("Plus");
+        }
+        let name = parts.pop().filter(|n| !n.is_empty()).ok_or("leere Taste")?;
+
+        let mut chord = KeyChord {
+            key: Key::Escape,
+            ctrl: false,
+            alt: false,
+            shift: None,
+        };
+        for modifier in parts {
+            match modifier.to_lowercase().as_str() {
+                "ctrl" | "control" => chord.ctrl = true,
+                "alt" => chord.alt = true,
+                "shift" => chord.shift = Some(true),
+                other => return Err(format!("unbekannter Modifikator „{other}“")),
+            }
+        }
+
+        let mut chars = name.chars();
+        match (chars.next(), chars.next()) {
+            // Einzelne Buchstaben: Großschreibung bedeutet Shift
+            (Some(c), None) if c.is_ascii_alphabetic() => {
+                chord.key = Key::from_name(&c.to_ascii_uppercase().to_string())
+                    .ok_or_else(|| format!("unbekannte Taste „{name}“"))?;
+                if chord.shift.is_none() {
+                    chord.shift = Some(c.is_ascii_uppercase());
+                }
+            }
+            _ => {
+                chord.key =
+                    Key::from_name(name).ok_or_else(|| format!("unbekannte Taste „{name}“"))?;
+            }
+        }
+
+        Ok(chord)
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ctrl {
+            write!(f, "Ctrl+")?;
+        }
+        if self.alt {
+            write!(f, "Alt+")?;
+        }
+
+        let name = self.key.name();
+        let is_letter = name.len() == 1 && name.chars().all(|c| c.is_ascii_alphabetic());
+        match (is_letter, self.shift) {
+            (true, Some(true)) => write!(f, "{}", name.to_ascii_uppercase()),
+            (true, _) => write!(f, "{}", name.to_ascii_lowercase()),
+            (false, Some(true)) => write!(f, "Shift+{name}"),
+            (false, _) => write!(f, "{name}"),
+        }
+    }
+}
+
+/// Folge von Tasten, z. B. `g g` oder `d d`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeySequence(pub Vec<KeyChord>);
+
+impl KeySequence {
+    /// Parst die Schreibweise aus der Konfiguration.
+    ///
+    /// Tasten werden durch Leerzeichen getrennt; Buchstabenketten wie `gg`
+    /// werden als Folge einzelner Tasten gelesen.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut chords = Vec::new();
+        for token in input.split_whitespace() {
+            match KeyChord::parse(token) {
+                Ok(chord) => chords.push(chord),
+                Err(_) if token.chars().all(|c| c.is_ascii_alphanumeric()) => {
+                    for c in token.chars() {
+                        chords.push(KeyChord::parse(&c.to_string())?);
+                    }
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        if chords.is_empty() {
+            return Err("leere Tastenfolge".into());
+        }
+        Ok(Self(chords))
+    }
+}
+
+impl fmt::Display for KeySequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<String> = self.0.iter().map(ToString::to_string).collect();
+        write!(f, "{}", parts.join(" "))
+    }
+}
+
+impl Serialize for KeySequence {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for KeySequence {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        KeySequence::parse(&raw).map_err(|e| de::Error::custom(format!("„{raw}“: {e}")))
+    }
+}
+
+/// Tastenbelegung der GUI: Aktion → Liste von Tastenfolgen
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct Keymap(pub BTreeMap<Action, Vec<KeySequence>>);
+
+impl Default for Keymap {
+    /// Vim-Belegung plus die klassischen Pfeil-/Funktionstasten
+    fn default() -> Self {
+        let bindings: &[(Action, &[&str])] = &[
+            (Action::Next, &["j", "Down"]),
+            (Action::Prev, &["k", "Up"]),
+            (Action::PageDown, &["Ctrl+d", "PageDown"]),
+            (Action::PageUp, &["Ctrl+u", "PageUp"]),
+            (Action::Top, &["g g", "Home"]),
+            (Action::Bottom, &["G", "End"]),
+            (Action::Select, &["Enter"]),
+            (Action::SelectAndPaste, &["Ctrl+Enter"]),
+            (Action::Delete, &["d d", "Delete"]),
+            (Action::Pin, &["p"]),
+            (Action::ToggleMark, &["x", "Ctrl+Space"]),
+            (Action::MarkNext, &["J", "Shift+Down"]),
+            (Action::MarkPrev, &["K", "Shift+Up"]),
+            (Action::Merge, &["m"]),
+            (Action::Edit, &["e", "F2"]),
+            (Action::Transform, &["t"]),
+            (Action::SwitchTab, &["Tab"]),
+            (Action::Preview, &["Space", "F3"]),
+            (Action::SearchFocus, &["/"]),
+            (Action::Quit, &["q", "Escape"]),
+        ];
+
+        let map = bindings
+            .iter()
+            .map(|(action, keys)| {
+                let sequences = keys
+                    .iter()
+                    .map(|k| KeySequence::parse(k).expect("Standardbelegung ist gültig"))
+                    .collect();
+                (*action, sequences)
+            })
+            .collect();
+        Self(map)
+    }
+}
+
+impl<'de> Deserialize<'de> for Keymap {
+    /// Konfigurierte Aktionen ersetzen die Standardbelegung, alle anderen bleiben erhalten
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let configured = BTreeMap::<Action, Vec<KeySequence>>::deserialize(deserializer)?;
+        let mut keymap = Keymap::default();
+        keymap.0.extend(configured);
+        Ok(keymap)
+    }
+}
+
+/// Ergebnis eines Tastendrucks im [`KeySequencer`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyOutcome {
+    /// Eine Tastenfolge ist vollständig
+    Action(Action),
+    /// Angefangene Folge, weitere Tasten erwartet
+    Pending,
+    /// Keine Belegung passt
+    Unbound,
+}
+
+/// Sammelt Tastendrücke, bis sie eine Folge aus der [`Keymap`] ergeben
+#[derive(Debug, Default)]
+pub struct KeySequencer {
+    pending: Vec<(Key, Modifiers)>,
+    last_press: Option<Instant>,
+}
+
+impl KeySequencer {
+    /// Maximale Pause zwischen zwei Tasten einer Folge
+    const TIMEOUT: Duration = Duration::from_millis(1000);
+
+    pub fn feed(&mut self, keymap: &Keymap, key: Key, modifiers: Modifiers) -> KeyOutcome {
+        if self.last_press.is_some_and(|t| t.elapsed() > Self::TIMEOUT) {
+            self.pending.clear();
+        }
+        self.last_press = Some(Instant::now());
+        self.pending.push((key, modifiers));
+        let was_sequence = self.pending.len() > 1;
+
+        match self.lookup(keymap) {
+            KeyOutcome::Unbound if was_sequence => {
+                // Angefangene Folge verwerfen und die letzte Taste alleine probieren
+                self.pending.clear();
+                self.pending.push((key, modifiers));
+                self.lookup(keymap)
+            }
+            outcome => outcome,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.pending.clear();
+    }
+
+    fn lookup(&mut self, keymap: &Keymap) -> KeyOutcome {
+        let mut is_prefix = false;
+        // Bei mehreren Treffern gewinnt die genauere Belegung (`Shift+Down` vor `Down`)
+        let mut best: Option<(usize, Action)> = None;
+
+        for (action, sequences) in &keymap.0 {
+            for seq in sequences {
+                if seq.0.len() < self.pending.len() {
+                    continue;
+                }
+                let prefix_matches = seq
+                    .0
+                    .iter()
+                    .zip(&self.pending)
+                    .all(|(chord, (key, mods))| chord.matches(*key, *mods));
+                if !prefix_matches {
+                    continue;
+                }
+                if seq.0.len() == self.pending.len() {
+                    let specificity = seq.0.iter().filter(|c| c.shift.is_some()).count();
+                    if best.is_none_or(|(s, _)| specificity > s) {
+                        best = Some((specificity, *action));
+                    }
+                    continue;
+                }
+                is_prefix = true;
+            }
+        }
+
+        if let Some((_, action)) = best {
+            self.pending.clear();
+            KeyOutcome::Action(action)
+        } else if is_prefix {
+            KeyOutcome::Pending
+        } else {
+            self.pending.clear();
+            KeyOutcome::Unbound
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shift() -> Modifiers {
+        Modifiers {
+            shift: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parses_letters_modifiers_and_sequences() {
+        let seq = KeySequence::parse("G").unwrap();
+        assert_eq!(seq.0[0].key, Key::G);
+        assert_eq!(seq.0[0].shift, Some(true));
+
+        let seq = KeySequence::parse("Ctrl+d").unwrap();
+        assert!(seq.0[0].ctrl);
+        assert_eq!(seq.0[0].shift, Some(false));
+
+        assert_eq!(KeySequence::parse("gg").unwrap().0.len(), 2);
+        assert_eq!(KeySequence::parse("g g").unwrap().0.len(), 2);
+        assert_eq!(KeySequence::parse("Down").unwrap().0[0].key, Key::ArrowDown);
+        assert!(KeySequence::parse("Hyper+x").is_err());
+    }
+
+    #[test]
+    fn display_roundtrip() {
+        for raw in ["g g", "G", "Ctrl+d", "Escape", "Alt+1"] {
+            let seq = KeySequence::parse(raw).unwrap();
+            assert_eq!(KeySequence::parse(&seq.to_string()).unwrap(), seq);
+        }
+    }
+
+    #[test]
+    fn unknown_action_is_rejected() {
+        let err = toml::from_str::<Keymap>("fly = [\"f\"]").unwrap_err();
+        assert!(err.to_string().contains("fly"));
+    }
+
+    #[test]
+    fn configured_actions_override_defaults() {
+        let keymap: Keymap = toml::from_str("next = [\"n\"]").unwrap();
+        assert_eq!(
+            keymap.0[&Action::Next],
+            vec![KeySequence::parse("n").unwrap()]
+        );
+        assert_eq!(keymap.0[&Action::Prev], Keymap::default().0[&Action::Prev]);
+    }
+
+    #[test]
+    fn sequencer_handles_multi_key_bindings() {
+        let keymap = Keymap::default();
+        let mut seq = KeySequencer::default();
+        let none = Modifiers::NONE;
+
+        assert_eq!(seq.feed(&keymap, Key::G, none), KeyOutcome::Pending);
+        assert_eq!(
+            seq.feed(&keymap, Key::G, none),
+            KeyOutcome::Action(Action::Top)
+        );
+        assert_eq!(
+            seq.feed(&keymap, Key::G, shift()),
+            KeyOutcome::Action(Action::Bottom)
+        );
+
+        // abgebrochene Folge: "g" gefolgt von "j" führt "j" aus
+        assert_eq!(seq.feed(&keymap, Key::G, none), KeyOutcome::Pending);
+        assert_eq!(
+            seq.feed(&keymap, Key::J, none),
+            KeyOutcome::Action(Action::Next)
+        );
+        assert_eq!(seq.feed(&keymap, Key::Z, none), KeyOutcome::Unbound);
+    }
+
+    #[test]
+    fn explicit_shift_binding_wins() {
+        let keymap = Keymap::default();
+        let mut seq = KeySequencer::default();
+
+        assert_eq!(
+            seq.feed(&keymap, Key::ArrowDown, shift()),
+            KeyOutcome::Action(Action::MarkNext)
+        );
+        assert_eq!(
+            seq.feed(&keymap, Key::ArrowDown, Modifiers::NONE),
+            KeyOutcome::Action(Action::Next)
+        );
+    }
+}
@@ -0,0 +1,285 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Erkannte Art eines Clipboard-Eintrags
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContentKind {
+    #[default]
+    Text,
+    Url,
+    Email,
+    Path,
+    Json,
+    /// Quelltext mit geratener Sprache als Dateiendung (`rs`, `py`, `sh`, …)
+    Code(String),
+    Color,
+    Uuid,
+    Image,
+}
+
+impl ContentKind {
+    /// Kurzes Symbol für die Listenansicht
+    pub fn badge(&self) -> &'static str {
+        match self {
+            ContentKind::Text => "📝",
+            ContentKind::Url => "🔗",
+            ContentKind::Email => "✉",
+            ContentKind::Path => "📁",
+            ContentKind::Json => "{}",
+            ContentKind::Code(_) => "⌨",
+            ContentKind::Color => "🎨",
+            ContentKind::Uuid => "🆔",
+            ContentKind::Image => "🖼",
+        }
+    }
+
+    /// Name für Filter (`kind:url`) und Anzeige
+    pub fn name(&self) -> &'static str {
+        match self {
+            ContentKind::Text => "text",
+            ContentKind::Url => "url",
+            ContentKind::Email => "email",
+            ContentKind::Path => "path",
+            ContentKind::Json => "json",
+            ContentKind::Code(_) => "code",
+            ContentKind::Color => "color",
+            ContentKind::Uuid => "uuid",
+            ContentKind::Image => "image",
+        }
+    }
+
+    /// Sprache für das Syntax-Highlighting, falls sinnvoll
+    pub fn language(&self) -> Option<&str> {
+        match self {
+            ContentKind::Json => Some("json"),
+            ContentKind::Code(lang) => Some(lang),
+            _ => None,
+        }
+    }
+
+    /// Prüft einen Filter wie `code`, `code:rs` oder `json`
+    pub fn matches_filter(&self, filter: &str) -> bool {
+        let filter = filter.to_lowercase();
+        match filter.split_once(':') {
+            Some(("code", lang)) => self.language() == Some(lang) && self.name() == "code",
+            _ => self.name() == filter,
+        }
+    }
+}
+
+impl fmt::Display for ContentKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContentKind::Code(lang) => write!(f, "code:{lang}"),
+            other => write!(f, "{}", other.name()),
+        }
+    }
+}
+
+/// Ermittelt die Art eines Texteintrags anhand einfacher Heuristiken
+pub fn detect(text: &str) -> ContentKind {
+    let trimmed = text.trim();
+    let single_line = !trimmed.contains('\n');
+
+    if single_line && !trimmed.contains(char::is_whitespace) {
+        if is_url(trimmed) {
+            return ContentKind::Url;
+        }
+        if is_email(trimmed) {
+            return ContentKind::Email;
+        }
+        if is_hex_color(trimmed) {
+            return ContentKind::Color;
+        }
+        if is_uuid(trimmed) {
+            return ContentKind::Uuid;
+        }
+    }
+
+    if single_line && is_path(trimmed) {
+        return ContentKind::Path;
+    }
+
+    if (trimmed.starts_with('{') || trimmed.starts_with('['))
+        && serde_json::from_str::<serde_json::Value>(trimmed).is_ok()
+    {
+        return ContentKind::Json;
+    }
+
+    match guess_language(trimmed) {
+        Some(lang) => ContentKind::Code(lang.to_string()),
+        None => ContentKind::Text,
+    }
+}
+
+fn is_url(s: &str) -> bool {
+    ["http://", "https://", "ftp://", "file://"]
+        .iter()
+        .any(|scheme| s.len() > scheme.len() && s.to_lowercase().starts_with(scheme))
+        || (s.starts_with("www.") && s[4..].contains('.'))
+}
+
+fn is_email(s: &str) -> bool {
+    let Some((local, domain)) = s.split_once('@') else {
+        return false;
+    };
+    !local.is_empty()
+        && !domain.contains('@')
+        && domain.contains('.')
+        && !domain.starts_with('.')
+        && !domain.ends_with('.')
+        && local
+            .chars()
+            .all(|c| c.is_alphanumeric() || "._%+-".contains(c))
+        && domain
+            .chars()
+            .all(|c| c.is_alphanumeric() || ".-".contains(c))
+}
+
+fn is_hex_color(s: &str) -> bool {
+    s.strip_prefix('#').is_some_and(|hex| {
+        matches!(hex.len(), 3 | 4 | 6 | 8) && hex.chars().all(|c| c.is_ascii_hexdigit())
+    })
+}
+
+fn is_uuid(s: &str) -> bool {
+    let groups: Vec<&str> = s.split('-').collect();
+    groups.len() == 5
+        && groups
+            .iter()
+            .zip([8, 4, 4, 4, 12])
+            .all(|(g, len)| g.len() == len && g.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+fn is_path(s: &str) -> bool {
+    let prefixed = ["/", "~/", "./", "../"].iter().any(|p| s.starts_with(p));
+    // "/" alleine oder Kommentare wie "// foo" sind keine Pfade
+    prefixed && s.len() > 1 && !s.starts_with("//") && !s.contains("  ")
+}
+
+/// Grobe Sprach-Erkennung; liefert die Dateiendung für das Highlighting
+fn guess_language(s: &str) -> Option<&'static str> {
+    let first = s.lines().next().unwrap_or_default().trim();
+    let lower = s.to_lowercase();
+    let has = |needle: &str| s.contains(needle);
+
+    if first.starts_with("#!") {
+        return Some(if first.contains("python") { "py" } else { "sh" });
+    }
+    if first.starts_with("<?xml") || (first.starts_with('<') && s.trim_end().ends_with('>')) {
+        return Some(if lower.contains("<html") || lower.contains("<div") {
+            "html"
+        } else {
+            "xml"
+        });
+    }
+    if has("fn ") && (has("->") || has("let ") || has("::") || has("impl ") || has("pub ")) {
+        return Some("rs");
+    }
+    if has("#include") {
+        return Some("cpp");
+    }
+    if has("package ") && has("func ") {
+        return Some("go");
+    }
+    if (has("def ") || has("import ")) && s.lines().any(|l| l.trim_end().ends_with(':'))
+        || has("self.") && has("def ")
+    {
+        return Some("py");
+    }
+    if has("function ") || has("=>") && (has("const ") || has("let ")) || has("console.log") {
+        return Some("js");
+    }
+
+    let sql_start = [
+        "select ", "insert ", "update ", "delete ", "create ", "alter ", "with ",
+    ];
+    let sql_body = [" from ", " into ", " set ", " table ", " where ", " values"];
+    if sql_start.iter().any(|k| lower.starts_with(k))
+        && sql_body
+            .iter()
+            .any(|k| lower.replace('\n', " ").contains(k))
+    {
+        return Some("sql");
+    }
+
+    let shell_commands = [
+        "sudo ",
+        "cd ",
+        "git ",
+        "cargo ",
+        "ls ",
+        "echo ",
+        "export ",
+        "curl ",
+        "docker ",
+        "systemctl ",
+        "make ",
+        "npm ",
+        "pacman ",
+        "yay ",
+        "rm ",
+        "cp ",
+        "mv ",
+        "mkdir ",
+    ];
+    if first.starts_with("$ ")
+        || shell_commands.iter().any(|c| first.starts_with(c))
+            && (s.lines().count() > 1 || has(" | ") || has(" && ") || has(" --") || has(" -"))
+    {
+        return Some("sh");
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_simple_kinds() {
+        assert_eq!(detect("https://example.org/a?b=c"), ContentKind::Url);
+        assert_eq!(detect("max.mustermann@example.de"), ContentKind::Email);
+        assert_eq!(detect("#1e1e2e"), ContentKind::Color);
+        assert_eq!(
+            detect("123e4567-e89b-12d3-a456-426614174000"),
+            ContentKind::Uuid
+        );
+        assert_eq!(detect("~/.config/hyprclip/config.toml"), ContentKind::Path);
+        assert_eq!(detect("{\"a\": [1, 2]}"), ContentKind::Json);
+        assert_eq!(detect("Hallo Welt"), ContentKind::Text);
+        assert_eq!(detect("#zzz"), ContentKind::Text);
+    }
+
+    #[test]
+    fn guesses_code_languages() {
+        assert_eq!(
+            detect("fn main() -> Result<(), ()> {\n    Ok(())\n}"),
+            ContentKind::Code("rs".into())
+        );
+        assert_eq!(
+            detect("SELECT id, name\nFROM users WHERE id = 1;"),
+            ContentKind::Code("sql".into())
+        );
+        assert_eq!(
+            detect("git log --oneline | head"),
+            ContentKind::Code("sh".into())
+        );
+        assert_eq!(
+            detect("def foo(self):\n    return 1"),
+            ContentKind::Code("py".into())
+        );
+    }
+
+    #[test]
+    fn filters_match_kind_and_language() {
+        let rust = ContentKind::Code("rs".into());
+        assert!(rust.matches_filter("code"));
+        assert!(rust.matches_filter("code:rs"));
+        assert!(!rust.matches_filter("code:py"));
+        assert!(ContentKind::Url.matches_filter("URL"));
+        assert_eq!(rust.to_string(), "code:rs");
+    }
+}
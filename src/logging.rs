@@ -0,0 +1,93 @@
+use std::{
+    fmt,
+    sync::atomic::{AtomicBool, Ordering},
+};
+use tracing_subscriber::EnvFilter;
+
+/// Vorgabe ohne `--log-level` und `RUST_LOG`: eigene Meldungen ab `info`, fremde Crates ab `warn`
+const DEFAULT_FILTER: &str = "warn,hyprclip=info";
+
+/// Clipboard-Inhalte dürfen (auf `trace`-Ebene) im Klartext erscheinen
+static LOG_CONTENTS: AtomicBool = AtomicBool::new(false);
+
+/// Ausgabeformat der Logs auf stderr
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum LogFormat {
+    /// Lesbarer Text; unter systemd ohne Zeitstempel und Farben
+    #[default]
+    Text,
+    /// Eine JSON-Zeile pro Ereignis
+    Json,
+}
+
+/// Richtet das Logging ein.
+///
+/// `level` ist ein Level (`debug`) für hyprclip oder eine vollständige Filterangabe wie bei
+/// `RUST_LOG` (`hyprclip::watcher=trace,zbus=info`); ohne Angabe gilt `RUST_LOG`.
+pub fn init(level: Option<&str>, format: LogFormat, log_contents: bool) {
+    LOG_CONTENTS.store(log_contents, Ordering::Relaxed);
+
+    let filter = match level {
+        Some(level) => EnvFilter::try_new(filter_directive(level))
+            .map_err(|e| eprintln!("Ungültiges Log-Level „{level}“: {e}"))
+            .ok(),
+        None => EnvFilter::try_from_default_env().ok(),
+    }
+    .unwrap_or_else(|| EnvFilter::new(DEFAULT_FILTER));
+
+    // journald setzt eigene Zeitstempel und versteht keine ANSI-Farben
+    let journald = std::env::var_os("JOURNAL_STREAM").is_some();
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .with_ansi(!journald);
+
+    let result = match (format, journald) {
+        (LogFormat::Json, _) => builder.json().try_init(),
+        (LogFormat::Text, true) => builder.without_time().try_init(),
+        (LogFormat::Text, false) => builder.try_init(),
+    };
+    if let Err(e) = result {
+        eprintln!("Logging konnte nicht eingerichtet werden: {e}");
+    }
+}
+
+/// Ein einzelnes Level gilt nur für hyprclip, alles andere wird als Filter übernommen
+fn filter_directive(level: &str) -> String {
+    if level.contains(['=', ',']) {
+        level.to_string()
+    } else {
+        format!("warn,hyprclip={level}")
+    }
+}
+
+/// Clipboard-Inhalt für Logs: nur mit `--log-contents` im Klartext, sonst die Länge.
+///
+/// Inhalte werden ausschließlich mit `trace!` geloggt.
+pub struct Redacted<'a>(pub &'a str);
+
+impl fmt::Display for Redacted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if LOG_CONTENTS.load(Ordering::Relaxed) {
+            write!(f, "{:?}", self.0)
+        } else {
+            write!(f, "<{} Zeichen>", self.0.chars().count())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_level_applies_to_hyprclip_only() {
+        assert_eq!(filter_directive("debug"), "warn,hyprclip=debug");
+        assert_eq!(filter_directive("zbus=info"), "zbus=info");
+    }
+
+    #[test]
+    fn contents_are_redacted_by_default() {
+        assert_eq!(Redacted("geheim").to_string(), "<6 Zeichen>");
+    }
+}
@@ -2,21 +2,46 @@ mod clear;
 mod clipboard;
 mod clipboard_state;
 mod config;
+mod dbus;
+mod edit;
 mod error;
+mod fswatch;
 mod history;
+mod hooks;
+mod hyprland;
+mod keymap;
+mod kind;
+mod logging;
+mod notifications;
+mod paste;
+mod queue;
+mod service;
+mod snippets;
+mod state;
+mod status;
+mod thumbnail;
+mod transform;
 mod ui;
 mod util;
 mod watcher;
-mod waybar;
 
-use crate::{error::AnyResult, watcher::get_latest_entry};
-use clap::Parser;
+use crate::{
+    error::{Error, Result},
+    watcher::get_latest_entry,
+};
+use clap::{Parser, Subcommand};
 use config::Config;
 use history::History;
 use std::{
+    collections::{hash_map::Entry, HashMap},
     fs::OpenOptions,
+    io::{self, IsTerminal, Write},
+    path::PathBuf,
+    process::ExitCode,
     sync::{Arc, Mutex},
+    time::Duration,
 };
+use tracing::{debug, info, warn};
 
 /// Hyprclip – Clipboard Manager mit GUI und Waybar-Integration
 #[derive(Parser)]
@@ -24,10 +49,14 @@ use std::{
 #[command(version = "0.1.0")]
 #[command(about = "Clipboard Manager mit GUI, Waybar-Modul und Watcher", long_about = None)]
 struct Cli {
-    /// Starte im Waybar-Modul-Modus (gibt JSON aus)
+    /// Starte im Waybar-Modul-Modus (gibt JSON aus); Kurzform für `status --format waybar`
     #[arg(long)]
     waybar: bool,
 
+    /// Mit --waybar: läuft weiter und gibt bei jeder Änderung eine neue JSON-Zeile aus
+    #[arg(long, requires = "waybar")]
+    follow: bool,
+
     /// Starte den Hintergrunddienst zur Clipboard-Überwachung
     #[arg(long)]
     watch: bool,
@@ -51,18 +80,237 @@ struct Cli {
     /// Gibt letzten eintrag aus
     #[arg(long)]
     last: bool,
+
+    /// Zusätzliche Konfigurationsdatei; ihre Werte gehen vor config.toml
+    #[arg(long, global = true, value_name = "PATH")]
+    config: Option<PathBuf>,
+
+    /// Setzt einen Konfigurationswert, z. B. `--option window.width=640`; geht allem anderen vor
+    #[arg(long = "option", global = true, value_name = "KEY=WERT")]
+    options: Vec<String>,
+
+    /// Log-Level (`error` … `trace`) oder Filter wie bei RUST_LOG, z. B. `hyprclip::watcher=debug`
+    #[arg(long, global = true, value_name = "LEVEL")]
+    log_level: Option<String>,
+
+    /// Format der Log-Ausgabe auf stderr
+    #[arg(long, global = true, value_enum, default_value = "text")]
+    log_format: logging::LogFormat,
+
+    /// Clipboard-Inhalte auf `trace`-Ebene im Klartext loggen (sonst nur die Länge)
+    #[arg(long, global = true)]
+    log_contents: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Setzt einen Eintrag aus dem Verlauf ins Clipboard
+    Copy {
+        /// Position im Verlauf (0 = neuester Eintrag)
+        id: usize,
+        /// Fügt den Eintrag anschließend ins fokussierte Fenster ein
+        #[arg(long)]
+        paste: bool,
+    },
+    /// Bearbeitet einen Texteintrag in $EDITOR und legt das Ergebnis ins Clipboard
+    Edit {
+        /// Position im Verlauf (0 = neuester Eintrag)
+        id: usize,
+        /// Ersetzt den Eintrag, statt einen neuen anzulegen
+        #[arg(long)]
+        replace: bool,
+    },
+    /// Wandelt einen Texteintrag um (neuer Eintrag + Clipboard); ohne Namen: Liste
+    Transform {
+        /// Name der Umwandlung, z. B. `upper` oder `json-pretty`
+        name: Option<String>,
+        /// Position im Verlauf (0 = neuester Eintrag)
+        #[arg(default_value_t = 0)]
+        id: usize,
+    },
+    /// Status für Leisten: Waybar, i3blocks, i3bar, polybar, eww/ironbar
+    Status {
+        #[arg(long, value_enum, default_value = "waybar")]
+        format: status::StatusFormat,
+        /// Läuft weiter und gibt bei jeder Änderung eine neue Zeile aus
+        #[arg(long)]
+        follow: bool,
+    },
+    /// Hält die Aufzeichnung neuer Kopien an
+    Pause,
+    /// Setzt die Aufzeichnung fort
+    Resume,
+    /// Warteschlange: mehrere Kopien sammeln und nacheinander einfügen
+    Queue {
+        #[command(subcommand)]
+        action: QueueCommand,
+    },
+    /// Textbausteine aus snippets.toml
+    Snippet {
+        #[command(subcommand)]
+        action: SnippetCommand,
+    },
+    /// Konfiguration prüfen, anzeigen oder bearbeiten
+    Config {
+        #[command(subcommand)]
+        action: ConfigCommand,
+    },
+    /// systemd-User-Unit für den Watcher verwalten
+    Service {
+        #[command(subcommand)]
+        action: ServiceCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum QueueCommand {
+    /// Sammelt die nächsten Kopien für das Einfügen nacheinander
+    Start {
+        /// Anzahl Kopien (Vorgabe: queue.size aus der Konfiguration)
+        count: Option<usize>,
+        /// Reihenfolge beim Einfügen (Vorgabe: queue.order)
+        #[arg(long, value_enum)]
+        order: Option<queue::QueueOrder>,
+    },
+    /// Setzt den nächsten Eintrag der Warteschlange ins Clipboard
+    Next {
+        /// Fügt ihn anschließend ins fokussierte Fenster ein
+        #[arg(long)]
+        paste: bool,
+    },
+    /// Zeigt, wie viele Einträge noch warten
+    Status,
+    /// Beendet den Modus und verwirft die Warteschlange
+    Stop,
+}
+
+#[derive(Subcommand)]
+enum SnippetCommand {
+    /// Listet alle Snippets auf
+    List {
+        /// Nur Snippets mit diesem Tag
+        #[arg(long)]
+        tag: Option<String>,
+    },
+    /// Setzt ein Snippet mit ausgefüllten Platzhaltern ins Clipboard
+    Copy {
+        name: String,
+        /// Wert für einen `{input:…}`-Platzhalter, sonst wird nachgefragt
+        #[arg(long = "set", value_name = "NAME=WERT")]
+        set: Vec<String>,
+        /// Fügt das Ergebnis anschließend ins fokussierte Fenster ein
+        #[arg(long)]
+        paste: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigCommand {
+    /// Prüft config.toml auf Syntaxfehler, unbekannte Schlüssel und ungültige Werte
+    Check,
+    /// Gibt die wirksame Konfiguration inklusive Standardwerten aus
+    Show {
+        /// Zeigt zu jedem Wert, woher er stammt (Standard, Datei, Umgebung, Kommandozeile)
+        #[arg(long)]
+        sources: bool,
+    },
+    /// Öffnet config.toml in $EDITOR und prüft sie danach
+    Edit,
+    /// Gibt den Pfad der config.toml aus (mit --config deren Pfad)
+    Path,
+}
+
+#[derive(Subcommand)]
+enum ServiceCommand {
+    /// Schreibt die Unit nach ~/.config/systemd/user und startet sie mit der Sitzung
+    Install,
+    /// Stoppt den Watcher und entfernt die Unit
+    Uninstall,
+    /// Zeigt, ob die Unit installiert ist und läuft
+    Status,
 }
 
 #[tokio::main]
-async fn main() -> AnyResult<()> {
+async fn main() -> ExitCode {
     let cli = Cli::parse();
-    let cfg = Config::load_or_create();
+    logging::init(cli.log_level.as_deref(), cli.log_format, cli.log_contents);
+
+    // Einzige Stelle, an der Fehler für den Benutzer ausgegeben werden
+    match run(cli).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(Error::UserAbort) => {
+            eprintln!("Abgebrochen.");
+            Error::UserAbort.into()
+        }
+        Err(e) => {
+            eprintln!("❌ {e}");
+            e.into()
+        }
+    }
+}
+
+async fn run(cli: Cli) -> Result<()> {
+    let mut overrides = config::Overrides {
+        config: cli.config,
+        options: Vec::new(),
+    };
+    for pair in cli.options {
+        let (key, value) = pair
+            .split_once('=')
+            .ok_or_else(|| Error::Invalid(format!("Erwartet KEY=WERT, erhalten „{pair}“")))?;
+        overrides
+            .options
+            .push((key.trim().to_string(), value.to_string()));
+    }
+    Config::set_overrides(overrides);
+
+    // Muss auch mit kaputter Konfiguration funktionieren
+    if let Some(Command::Config { action }) = cli.command {
+        return config_command(action);
+    }
+
+    let cfg = Config::load_or_create()?;
 
     let history = Arc::new(Mutex::new(History::load(
         &cfg.storage_path,
         cfg.history_limit,
     )));
 
+    if let Some(command) = cli.command {
+        match command {
+            Command::Copy { id, paste } => copy_entry(&history, &cfg, id, paste)?,
+            Command::Edit { id, replace } => edit::edit_entry(&history, &cfg, id, replace)?,
+            Command::Transform { name, id } => transform_entry(&history, &cfg, name, id)?,
+            Command::Status { format, follow } => status::run(&cfg, format, follow).await?,
+            Command::Pause | Command::Resume => {
+                let paused = matches!(command, Command::Pause);
+                let path = state::WatcherState::path(&cfg.storage_path);
+                state::WatcherState::update(&path, |s| s.paused = paused)
+                    .map_err(Error::storage(&path))?;
+                println!(
+                    "{}",
+                    if paused {
+                        "⏸️ Pausiert"
+                    } else {
+                        "▶️ Läuft wieder"
+                    }
+                );
+            }
+            Command::Queue { action } => queue_command(&cfg, action)?,
+            Command::Snippet { action } => snippet_command(&history, &cfg, action)?,
+            Command::Config { .. } => unreachable!("oben behandelt"),
+            Command::Service { action } => match action {
+                ServiceCommand::Install => service::install()?,
+                ServiceCommand::Uninstall => service::uninstall()?,
+                ServiceCommand::Status => service::status()?,
+            },
+        }
+        return Ok(());
+    }
+
     // 🔄 Aktionen mit sofortigem Rückgabewert
     if cli.clear {
         clear::clear_history(&mut history.lock().unwrap(), &cfg)?;
@@ -70,7 +318,11 @@ async fn main() -> AnyResult<()> {
     }
 
     if cli.export {
-        let json = history.lock().unwrap().export_json()?;
+        let json = history
+            .lock()
+            .unwrap()
+            .export_json()
+            .map_err(io::Error::from)?;
         println!("{json}");
         return Ok(());
     }
@@ -83,14 +335,14 @@ async fn main() -> AnyResult<()> {
         } else {
             println!("🔍 Treffer für „{}“:", keyword);
             for entry in results {
-                println!("- {}", entry.content);
+                println!("- [{}] {}", entry.kind, entry.content);
             }
         }
         return Ok(());
     }
 
     if cli.waybar {
-        waybar::run().await?;
+        status::run(&cfg, status::StatusFormat::Waybar, cli.follow).await?;
         return Ok(());
     }
 
@@ -100,13 +352,15 @@ async fn main() -> AnyResult<()> {
     }
 
     if cli.gui {
-        ui::launch_with_history(Arc::clone(&history), cfg.storage_path.clone())?;
+        if ui::launch_with_history(Arc::clone(&history), &cfg)? {
+            paste::paste_into_focused(&cfg.paste)?;
+        }
         return Ok(());
     }
 
     if cli.last {
         let last_entry = get_latest_entry().unwrap_or_else(|e| {
-            eprintln!("Error fetching last entry: {}", e);
+            warn!("Letzter Eintrag nicht lesbar: {e}");
             String::from("<no entry>")
         });
         print!("Last entry: {}", last_entry);
@@ -114,52 +368,311 @@ async fn main() -> AnyResult<()> {
     }
 
     // ❓ Fallback wenn kein Flag gesetzt
-    eprintln!("❗ Kein Modus gewählt. Starte mit --gui, --watch oder --help");
+    Err(Error::Invalid(
+        "Kein Modus gewählt. Starte mit --gui, --watch oder --help".into(),
+    ))
+}
+
+// 📋 Eintrag per CLI auswählen (optional direkt einfügen)
+fn copy_entry(history: &Mutex<History>, cfg: &Config, id: usize, paste: bool) -> Result<()> {
+    let entry = {
+        let mut guard = history.lock().unwrap();
+        let entry = guard
+            .promote(id)
+            .ok_or_else(|| Error::NotFound(format!("Kein Eintrag mit ID {id}")))?;
+        guard.save(&cfg.storage_path)?;
+        entry
+    };
+
+    clipboard::set_clipboard_item_no_ignore(&entry.item)?;
+
+    if paste {
+        paste::paste_into_focused(&cfg.paste)?;
+    } else {
+        // Der Clipboard-Inhalt lebt nur so lange wie dieser Prozess – dem Watcher Zeit zur Übernahme geben
+        std::thread::sleep(Duration::from_millis(cfg.paste.linger_ms));
+    }
+    Ok(())
+}
+
+// 🔀 Umwandlung per CLI anwenden bzw. verfügbare Umwandlungen auflisten
+fn transform_entry(
+    history: &Mutex<History>,
+    cfg: &Config,
+    name: Option<String>,
+    id: usize,
+) -> Result<()> {
+    let transforms = transform::Transforms::new(&cfg.transforms);
+    let Some(name) = name else {
+        for t in transforms.iter() {
+            println!("{:<20} {}", t.name(), t.description());
+        }
+        return Ok(());
+    };
+
+    let transform = transforms.get(&name).ok_or_else(|| {
+        Error::NotFound(format!(
+            "Unbekannte Umwandlung „{name}“ (Liste: hyprclip transform)"
+        ))
+    })?;
+    let entry = {
+        let mut guard = history.lock().unwrap();
+        let entry = transform::apply_to_entry(&mut guard, id, transform)?;
+        guard.save(&cfg.storage_path)?;
+        entry
+    };
+
+    clipboard::set_clipboard_item_no_ignore(&entry.item)?;
+    println!("{}", entry.content);
+    std::thread::sleep(Duration::from_millis(cfg.paste.linger_ms));
+    Ok(())
+}
+
+// ⚙️ Konfigurationsdatei prüfen, anzeigen, bearbeiten
+fn config_command(action: ConfigCommand) -> Result<()> {
+    match action {
+        ConfigCommand::Path => println!("{}", Config::primary_path().display()),
+        ConfigCommand::Show { sources: false } => {
+            let cfg = Config::load_or_create()?;
+            print!(
+                "{}",
+                toml::to_string_pretty(&cfg).map_err(|e| Error::Config(e.to_string()))?
+            );
+        }
+        ConfigCommand::Show { sources: true } => {
+            let (_, values) = Config::load_with_sources()?;
+            let lines: Vec<_> = values
+                .into_iter()
+                .map(|(key, value, source)| (format!("{key} = {value}"), source))
+                .collect();
+            let width = lines.iter().map(|(line, _)| line.chars().count()).max();
+            for (line, source) in &lines {
+                println!("{line:<width$}  # {source}", width = width.unwrap_or(0));
+            }
+        }
+        ConfigCommand::Check => {
+            let files = Config::files();
+            Config::load()?;
+            if files.is_empty() {
+                println!(
+                    "⚙️ Keine {} – es gelten die Standardwerte",
+                    Config::path().display()
+                );
+            }
+            for path in files {
+                println!("✅ {} ist gültig", path.display());
+            }
+        }
+        ConfigCommand::Edit => {
+            let path = Config::primary_path();
+            if !path.exists() {
+                Config::default().save().map_err(Error::storage(&path))?;
+            }
+            edit::open_editor(&path)?;
+            // Watcher und GUI übernehmen eine gültige Datei von selbst
+            Config::load()?;
+            println!("✅ {} ist gültig", path.display());
+        }
+    }
+    Ok(())
+}
+
+// 📚 Warteschlangen-Modus steuern
+fn queue_command(cfg: &Config, action: QueueCommand) -> Result<()> {
+    let path = queue::Queue::path(&cfg.storage_path);
+
+    match action {
+        QueueCommand::Start { count, order } => {
+            let count = count.unwrap_or(cfg.queue.size);
+            if count == 0 {
+                return Err(Error::Invalid("Anzahl muss größer als 0 sein".into()));
+            }
+            queue::Queue::new(count, order.unwrap_or(cfg.queue.order))
+                .save(&path)
+                .map_err(Error::storage(&path))?;
+            println!("📚 Sammle die nächsten {count} Kopien");
+        }
+        QueueCommand::Next { paste: do_paste } => {
+            let mut queue = queue::Queue::load(&path)
+                .ok_or_else(|| Error::NotFound("Warteschlange ist nicht aktiv".into()))?;
+            let item = queue
+                .next()
+                .ok_or_else(|| Error::NotFound("Warteschlange ist leer".into()))?;
+            queue.save(&path).map_err(Error::storage(&path))?;
+
+            clipboard::set_clipboard_item_no_ignore(&item)?;
+            if do_paste {
+                paste::paste_into_focused(&cfg.paste)?;
+            } else {
+                std::thread::sleep(Duration::from_millis(cfg.paste.linger_ms));
+            }
+        }
+        QueueCommand::Status => match queue::Queue::load(&path) {
+            Some(queue) if queue.is_collecting() => println!(
+                "📚 {} gesammelt, noch {} zu sammeln",
+                queue.depth(),
+                queue.remaining
+            ),
+            Some(queue) => println!("📚 {} Einträge warten", queue.depth()),
+            None => println!("📚 Warteschlange ist nicht aktiv"),
+        },
+        QueueCommand::Stop => {
+            queue::Queue::remove(&path).map_err(Error::storage(&path))?;
+            println!("📚 Warteschlange beendet");
+        }
+    }
+    Ok(())
+}
+
+// 🧩 Snippets auflisten bzw. ausgefüllt ins Clipboard legen
+fn snippet_command(history: &Mutex<History>, cfg: &Config, action: SnippetCommand) -> Result<()> {
+    let store = snippets::SnippetStore::load()?;
+
+    let (name, set, paste) = match action {
+        SnippetCommand::List { tag } => {
+            let query = tag.map(|t| format!("#{t}")).unwrap_or_default();
+            for index in store.filter(&query) {
+                let s = &store.snippets[index];
+                let tags = s.tags.iter().map(|t| format!("#{t}")).collect::<Vec<_>>();
+                println!("{:<20} {:<24} {}", s.name, tags.join(" "), s.description);
+            }
+            return Ok(());
+        }
+        SnippetCommand::Copy { name, set, paste } => (name, set, paste),
+    };
+
+    let snippet = store.get(&name).ok_or_else(|| {
+        Error::NotFound(format!(
+            "Unbekanntes Snippet „{name}“ (Liste: hyprclip snippet list)"
+        ))
+    })?;
+
+    let mut inputs = HashMap::new();
+    for pair in set {
+        let (key, value) = pair
+            .split_once('=')
+            .ok_or_else(|| Error::Invalid(format!("Erwartet NAME=WERT, erhalten „{pair}“")))?;
+        inputs.insert(key.to_string(), value.to_string());
+    }
+    for input in snippet.inputs() {
+        if let Entry::Vacant(slot) = inputs.entry(input) {
+            let value = prompt(slot.key())?;
+            slot.insert(value);
+        }
+    }
+
+    let text = snippet.expand(&inputs, || get_latest_entry().ok())?;
+    // Die Abfrage kann gedauert haben: frisch laden, statt Einträge des Watchers zu überschreiben
+    let item = {
+        let mut guard = history.lock().unwrap();
+        let mut current = History::load(&cfg.storage_path, guard.limit);
+        current.add_text(text.clone());
+        current.save(&cfg.storage_path)?;
+        *guard = current;
+        history::ClipboardItem::Text(text)
+    };
+
+    clipboard::set_clipboard_item_no_ignore(&item)?;
+    if paste {
+        paste::paste_into_focused(&cfg.paste)?;
+    } else {
+        std::thread::sleep(Duration::from_millis(cfg.paste.linger_ms));
+    }
     Ok(())
 }
 
+/// Fragt einen Platzhalter im Terminal ab
+fn prompt(name: &str) -> Result<String> {
+    let stdin = io::stdin();
+    if !stdin.is_terminal() {
+        return Err(Error::Invalid(format!(
+            "Wert für „{name}“ fehlt (--set {name}=…)"
+        )));
+    }
+    eprint!("{name}: ");
+    io::stderr().flush()?;
+    let mut line = String::new();
+    stdin.read_line(&mut line)?;
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
 // 🔐 Watcher-Modus mit Lockfile + Ctrl+C-Abbruch
-async fn run_watcher(history: Arc<Mutex<History>>, cfg: Config) -> AnyResult<()> {
+async fn run_watcher(history: Arc<Mutex<History>>, cfg: Config) -> Result<()> {
     use tokio::signal;
 
-    let lock_path = "/tmp/hyprclip.lock";
+    // Ohne Wayland gibt es nichts zu überwachen – als Fehler beenden, damit systemd neu startet
+    wayland_client::Connection::connect_to_env()
+        .map_err(|e| Error::Clipboard(format!("Keine Wayland-Verbindung: {e}")))?;
 
-    // Lock-Datei exklusiv erstellen
-    let _lock = match OpenOptions::new()
+    // flock statt Lock-Datei: der Kernel gibt es auch nach Absturz, SIGKILL oder
+    // Watchdog-Abbruch frei, ein Neustart durch systemd findet also kein altes Lock vor
+    let lock_path = dirs::runtime_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("hyprclip.lock");
+    let lock = OpenOptions::new()
         .write(true)
-        .create_new(true)
-        .open(lock_path)
-    {
-        Ok(file) => file,
-        Err(_) => {
-            eprintln!("⚠️ Watcher läuft bereits (Lockfile vorhanden).");
-            return Ok(());
+        .create(true)
+        .truncate(false)
+        .open(&lock_path)
+        .map_err(Error::storage(&lock_path))?;
+    if let Err(e) = lock.try_lock() {
+        return Err(match e {
+            std::fs::TryLockError::WouldBlock => Error::Invalid(format!(
+                "Watcher läuft bereits (Lock {} ist belegt)",
+                lock_path.display()
+            )),
+            std::fs::TryLockError::Error(e) => Error::storage(&lock_path)(e),
+        });
+    }
+    debug!(lock = %lock_path.display(), "Lock gehalten");
+
+    // 🚌 D-Bus-Schnittstelle – ohne Session-Bus läuft der Watcher trotzdem
+    let (reloads, config) = tokio::sync::watch::channel(cfg.clone());
+    let _dbus = match zbus::Connection::session().await {
+        Ok(connection) => match dbus::serve(connection, Arc::clone(&history), config).await {
+            Ok(service) => Some(service),
+            Err(e) => {
+                warn!(service = dbus::SERVICE, "D-Bus-Dienst nicht verfügbar: {e}");
+                None
+            }
+        },
+        Err(e) => {
+            warn!("Keine Verbindung zum Session-Bus: {e}");
+            None
         }
     };
 
-    println!("📋 Watcher läuft... (Beenden mit Ctrl+C)");
+    info!("Watcher läuft (Beenden mit Ctrl+C)");
+    // Wayland erreichbar und Verlauf geladen (siehe `main`)
+    service::notify_ready();
 
-    let watch_task = tokio::spawn({
+    let mut watch_task = tokio::spawn({
         let h = Arc::clone(&history);
         let c = cfg.clone();
-        async move {
-            watcher::watch::watch_clipboard(h, c).await;
-        }
+        async move { watcher::watch::watch_clipboard(h, c, reloads).await }
     });
 
-    // Auf Ctrl+C warten
-    signal::ctrl_c().await?;
-    println!("👋 Beenden...");
-
-    // Lock-Datei aktiv löschen
-    if let Err(e) = std::fs::remove_file(lock_path) {
-        eprintln!("❌ Konnte Lock-Datei nicht löschen: {e}");
-    } else {
-        println!("🧹 Lock-Datei gelöscht.");
+    // Auf Ctrl+C bzw. SIGTERM (systemctl stop) warten – oder auf das Ende der Überwachung,
+    // damit ein Absturz der Schleife den Prozess sofort mit Fehler beendet
+    let mut terminate = signal::unix::signal(signal::unix::SignalKind::terminate())?;
+    tokio::select! {
+        result = signal::ctrl_c() => result?,
+        _ = terminate.recv() => {}
+        result = &mut watch_task => {
+            service::notify_stopping();
+            return Err(match result {
+                Ok(Err(e)) => e,
+                Ok(Ok(())) => Error::Clipboard("Überwachung unerwartet beendet".to_string()),
+                Err(e) => Error::Clipboard(format!("Überwachung abgestürzt: {e}")),
+            });
+        }
     }
+    info!("Beende Watcher");
+    service::notify_stopping();
 
-    // Watcher-Task abbrechen (optional)
+    // Watcher-Task abbrechen; das Lock fällt mit dem Prozess
     watch_task.abort();
+    drop(lock);
 
     Ok(())
 }
@@ -0,0 +1,259 @@
+use crate::{
+    history::{ClipboardItem, Entry},
+    thumbnail,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+use tracing::{debug, warn};
+use zbus::{zvariant::Value, Connection};
+
+/// Zeichen der Vorschau im Benachrichtigungstext
+const PREVIEW_CHARS: usize = 120;
+
+/// Desktop-Benachrichtigungen (org.freedesktop.Notifications), je Anlass schaltbar
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NotificationConfig {
+    /// Neuer Eintrag mit Vorschau bzw. Vorschaubild
+    pub new_entry: bool,
+    /// Vertraulicher Inhalt wurde nicht gespeichert
+    pub sensitive: bool,
+    /// Fehler beim Speichern von Verlauf oder Bildern
+    pub errors: bool,
+    /// Aufzeichnung pausiert bzw. fortgesetzt
+    pub state: bool,
+    /// Mindestabstand zwischen zwei Meldungen desselben Anlasses
+    pub min_interval_ms: u64,
+    /// Anzeigedauer; -1 = Vorgabe des Benachrichtigungsdienstes
+    pub timeout_ms: i32,
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            new_entry: false,
+            sensitive: true,
+            errors: true,
+            state: true,
+            min_interval_ms: 2000,
+            timeout_ms: -1,
+        }
+    }
+}
+
+/// Anlass einer Benachrichtigung
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Category {
+    NewEntry,
+    Sensitive,
+    Error,
+    State,
+}
+
+impl NotificationConfig {
+    fn enabled(&self, category: Category) -> bool {
+        match category {
+            Category::NewEntry => self.new_entry,
+            Category::Sensitive => self.sensitive,
+            Category::Error => self.errors,
+            Category::State => self.state,
+        }
+    }
+
+    fn any_enabled(&self) -> bool {
+        self.new_entry || self.sensitive || self.errors || self.state
+    }
+}
+
+/// Lässt je Anlass höchstens eine Meldung pro Intervall durch. Zustandswechsel sind
+/// ausgenommen: sonst bliebe nach schnellem Pausieren und Fortsetzen „pausiert“ stehen.
+struct RateLimiter {
+    interval: Duration,
+    last: HashMap<Category, Instant>,
+}
+
+impl RateLimiter {
+    fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last: HashMap::new(),
+        }
+    }
+
+    fn allow(&mut self, category: Category, now: Instant) -> bool {
+        if category == Category::State {
+            return true;
+        }
+        match self.last.get(&category) {
+            Some(last) if now.duration_since(*last) < self.interval => false,
+            _ => {
+                self.last.insert(category, now);
+                true
+            }
+        }
+    }
+}
+
+/// Verschickt Benachrichtigungen im Hintergrund; ohne Session-Bus bleibt er stumm
+pub struct Notifier {
+    connection: Option<Connection>,
+    cfg: NotificationConfig,
+    limiter: Mutex<RateLimiter>,
+}
+
+impl Notifier {
+    pub async fn new(cfg: &NotificationConfig) -> Self {
+        let connection = if cfg.any_enabled() {
+            Connection::session()
+                .await
+                .map_err(|e| warn!("Benachrichtigungen deaktiviert: {e}"))
+                .ok()
+        } else {
+            None
+        };
+        Self {
+            connection,
+            cfg: cfg.clone(),
+            limiter: Mutex::new(RateLimiter::new(Duration::from_millis(cfg.min_interval_ms))),
+        }
+    }
+
+    pub fn new_entry(&self, entry: &Entry) {
+        match &entry.item {
+            ClipboardItem::Text(_) => self.send(
+                Category::NewEntry,
+                "📋 Neuer Eintrag",
+                &escape_markup(&entry.summary(PREVIEW_CHARS)),
+                None,
+            ),
+            ClipboardItem::Image(path) => {
+                let thumb = thumbnail::thumbnail_path(path);
+                let image = if thumb.exists() { thumb } else { path.clone() };
+                self.send(
+                    Category::NewEntry,
+                    "🖼️ Neues Bild",
+                    &escape_markup(&entry.summary(PREVIEW_CHARS)),
+                    Some(format!("file://{}", image.display())),
+                );
+            }
+        }
+    }
+
+    pub fn sensitive_skipped(&self) {
+        self.send(
+            Category::Sensitive,
+            "🔒 Vertraulicher Inhalt",
+            "Inhalt aus einem Passwortmanager wurde nicht gespeichert.",
+            None,
+        );
+    }
+
+    pub fn error(&self, message: &str) {
+        self.send(
+            Category::Error,
+            "⚠️ Hyprclip-Fehler",
+            &escape_markup(message),
+            None,
+        );
+    }
+
+    pub fn paused(&self, paused: bool) {
+        let summary = if paused {
+            "⏸️ Aufzeichnung pausiert"
+        } else {
+            "▶️ Aufzeichnung fortgesetzt"
+        };
+        self.send(Category::State, summary, "", None);
+    }
+
+    fn send(&self, category: Category, summary: &str, body: &str, image: Option<String>) {
+        let Some(connection) = &self.connection else {
+            return;
+        };
+        if !self.cfg.enabled(category)
+            || !self.limiter.lock().unwrap().allow(category, Instant::now())
+        {
+            return;
+        }
+
+        let connection = connection.clone();
+        let (summary, body, timeout) = (summary.to_string(), body.to_string(), self.cfg.timeout_ms);
+        // Nicht auf den Dienst warten – ohne laufenden Daemon kann das dauern
+        tokio::spawn(async move {
+            let mut hints: HashMap<&str, Value> = HashMap::new();
+            hints.insert("desktop-entry", Value::from("hyprclip"));
+            if category == Category::Error {
+                hints.insert("urgency", Value::from(2u8));
+            }
+            if let Some(image) = &image {
+                hints.insert("image-path", Value::from(image.as_str()));
+            }
+
+            let result = connection
+                .call_method(
+                    Some("org.freedesktop.Notifications"),
+                    "/org/freedesktop/Notifications",
+                    Some("org.freedesktop.Notifications"),
+                    "Notify",
+                    &(
+                        "Hyprclip",
+                        0u32,
+                        "edit-paste",
+                        summary.as_str(),
+                        body.as_str(),
+                        Vec::<&str>::new(),
+                        hints,
+                        timeout,
+                    ),
+                )
+                .await;
+            if let Err(e) = result {
+                debug!("Benachrichtigung fehlgeschlagen: {e}");
+            }
+        });
+    }
+}
+
+/// Der Benachrichtigungstext darf einfaches Markup enthalten
+fn escape_markup(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_limit_is_per_category() {
+        let mut limiter = RateLimiter::new(Duration::from_secs(2));
+        let start = Instant::now();
+        assert!(limiter.allow(Category::NewEntry, start));
+        assert!(!limiter.allow(Category::NewEntry, start + Duration::from_secs(1)));
+        assert!(limiter.allow(Category::Error, start + Duration::from_secs(1)));
+        assert!(limiter.allow(Category::NewEntry, start + Duration::from_secs(3)));
+
+        assert!(limiter.allow(Category::State, start));
+        assert!(limiter.allow(Category::State, start + Duration::from_millis(100)));
+    }
+
+    #[test]
+    fn categories_follow_config() {
+        let cfg = NotificationConfig::default();
+        assert!(!cfg.enabled(Category::NewEntry));
+        assert!(cfg.enabled(Category::Error));
+
+        let off = NotificationConfig {
+            sensitive: false,
+            errors: false,
+            state: false,
+            ..cfg
+        };
+        assert!(!off.any_enabled());
+    }
+}
@@ -0,0 +1,202 @@
+use crate::{
+    config::{PasteBackend, PasteConfig},
+    error::{Error, Result},
+    hyprland,
+};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    fmt,
+    process::Command,
+    time::{Duration, Instant},
+};
+use tracing::{info, warn};
+
+mod virtual_keyboard;
+
+/// Fensterklasse des Pickers, auf deren Verschwinden gewartet wird
+const PICKER_CLASS: &str = "hyprclip";
+
+/// Tastenkombination zum Einfügen, z. B. `Ctrl+v` oder `Ctrl+Shift+v`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Keystroke {
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+    pub super_key: bool,
+    /// XKB-Keysym-Name der Taste (`v`, `Insert`, …)
+    pub keysym: String,
+}
+
+impl Keystroke {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut parts: Vec<&str> = input.split('+').map(str::trim).collect();
+        let keysym = parts
+            .pop()
+            .filter(|k| !k.is_empty())
+            .ok_or_else(|| format!("„{input}“: Taste fehlt"))?;
+
+        let mut keystroke = Keystroke {
+            ctrl: false,
+            shift: false,
+            alt: false,
+            super_key: false,
+            keysym: keysym.to_string(),
+        };
+        for modifier in parts {
+            match modifier.to_lowercase().as_str() {
+                "ctrl" | "control" => keystroke.ctrl = true,
+                "shift" => keystroke.shift = true,
+                "alt" => keystroke.alt = true,
+                "super" | "logo" | "meta" => keystroke.super_key = true,
+                other => return Err(format!("„{input}“: unbekannter Modifikator „{other}“")),
+            }
+        }
+
+        Ok(keystroke)
+    }
+
+    fn wtype_modifiers(&self) -> Vec<&'static str> {
+        let mut mods = Vec::new();
+        if self.ctrl {
+            mods.push("ctrl");
+        }
+        if self.shift {
+            mods.push("shift");
+        }
+        if self.alt {
+            mods.push("alt");
+        }
+        if self.super_key {
+            mods.push("logo");
+        }
+        mods
+    }
+}
+
+impl fmt::Display for Keystroke {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (active, name) in [
+            (self.ctrl, "Ctrl"),
+            (self.shift, "Shift"),
+            (self.alt, "Alt"),
+            (self.super_key, "Super"),
+        ] {
+            if active {
+                write!(f, "{name}+")?;
+            }
+        }
+        write!(f, "{}", self.keysym)
+    }
+}
+
+impl Serialize for Keystroke {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Keystroke {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        Keystroke::parse(&raw).map_err(de::Error::custom)
+    }
+}
+
+/// Wartet, bis der Picker den Fokus abgegeben hat, und sendet dann den Einfüge-Tastendruck.
+///
+/// Die Tastenkombination richtet sich nach der Klasse des fokussierten Fensters
+/// (z. B. `Ctrl+Shift+v` für Terminals).
+pub fn paste_into_focused(cfg: &PasteConfig) -> Result<()> {
+    let class = wait_for_focus_return(Duration::from_millis(cfg.focus_timeout_ms));
+    std::thread::sleep(Duration::from_millis(cfg.delay_ms));
+
+    let keystroke = class
+        .as_deref()
+        .and_then(|c| cfg.per_class.get(c))
+        .unwrap_or(&cfg.keystroke);
+
+    info!(
+        %keystroke,
+        window = class.as_deref().unwrap_or("unbekannt"),
+        "Füge ein"
+    );
+    send_keystroke(keystroke, cfg.backend)?;
+
+    // Das Zielprogramm liest die Daten erst nach dem Tastendruck bei uns ab
+    std::thread::sleep(Duration::from_millis(cfg.linger_ms));
+    Ok(())
+}
+
+/// Gibt die Klasse des Fensters zurück, das nach dem Picker den Fokus hat
+fn wait_for_focus_return(timeout: Duration) -> Option<String> {
+    if !hyprland::is_running() {
+        // Ohne Compositor-Informationen bleibt nur eine feste Wartezeit
+        std::thread::sleep(timeout.min(Duration::from_millis(200)));
+        return None;
+    }
+
+    let start = Instant::now();
+    loop {
+        if let Some(window) = hyprland::active_window() {
+            if window.class != PICKER_CLASS {
+                return Some(window.class);
+            }
+        }
+        if start.elapsed() >= timeout {
+            return None;
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    }
+}
+
+fn send_keystroke(keystroke: &Keystroke, backend: PasteBackend) -> Result<()> {
+    match backend {
+        PasteBackend::VirtualKeyboard => virtual_keyboard::send(keystroke),
+        PasteBackend::Wtype => send_with_wtype(keystroke),
+        PasteBackend::Auto => virtual_keyboard::send(keystroke).or_else(|e| {
+            warn!("Virtuelle Tastatur nicht verfügbar ({e}), versuche wtype");
+            send_with_wtype(keystroke)
+        }),
+    }
+}
+
+fn send_with_wtype(keystroke: &Keystroke) -> Result<()> {
+    let mods = keystroke.wtype_modifiers();
+    let mut cmd = Command::new("wtype");
+    for m in &mods {
+        cmd.args(["-M", m]);
+    }
+    cmd.args(["-k", &keystroke.keysym]);
+    for m in mods.iter().rev() {
+        cmd.args(["-m", m]);
+    }
+
+    let status = cmd
+        .status()
+        .map_err(|e| Error::Paste(format!("wtype konnte nicht gestartet werden: {e}")))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(Error::Paste(format!("wtype beendet mit {status}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_keystrokes() {
+        let k = Keystroke::parse("Ctrl+Shift+v").unwrap();
+        assert!(k.ctrl && k.shift && !k.alt && !k.super_key);
+        assert_eq!(k.keysym, "v");
+        assert_eq!(k.to_string(), "Ctrl+Shift+v");
+
+        let k = Keystroke::parse("Shift+Insert").unwrap();
+        assert_eq!(k.wtype_modifiers(), vec!["shift"]);
+        assert_eq!(k.keysym, "Insert");
+
+        assert!(Keystroke::parse("Ctrl+").is_err());
+        assert!(Keystroke::parse("Hyper+v").is_err());
+    }
+}
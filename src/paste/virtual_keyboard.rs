@@ -0,0 +1,116 @@
+use super::Keystroke;
+use crate::error::{Error, Result};
+use std::{fmt, io::Write, os::fd::AsFd, time::Duration};
+use wayland_client::{
+    delegate_noop,
+    globals::{registry_queue_init, GlobalListContents},
+    protocol::{wl_registry, wl_seat::WlSeat},
+    Connection, Dispatch, QueueHandle,
+};
+use wayland_protocols_misc::zwp_virtual_keyboard_v1::client::{
+    zwp_virtual_keyboard_manager_v1::ZwpVirtualKeyboardManagerV1,
+    zwp_virtual_keyboard_v1::ZwpVirtualKeyboardV1,
+};
+
+/// `wl_keyboard.keymap_format.xkb_v1`
+const KEYMAP_FORMAT_XKB_V1: u32 = 1;
+/// Einziger Keycode unserer Keymap (`<K1> = 9` → evdev 1)
+const KEYCODE: u32 = 1;
+const KEY_PRESSED: u32 = 1;
+const KEY_RELEASED: u32 = 0;
+
+// Modifier-Masken der Standard-XKB-Modifier (Shift, Control, Mod1, Mod4)
+const MOD_SHIFT: u32 = 1;
+const MOD_CTRL: u32 = 1 << 2;
+const MOD_ALT: u32 = 1 << 3;
+const MOD_SUPER: u32 = 1 << 6;
+
+struct State;
+
+impl Dispatch<wl_registry::WlRegistry, GlobalListContents> for State {
+    fn event(
+        _: &mut Self,
+        _: &wl_registry::WlRegistry,
+        _: wl_registry::Event,
+        _: &GlobalListContents,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+delegate_noop!(State: ignore WlSeat);
+delegate_noop!(State: ZwpVirtualKeyboardManagerV1);
+delegate_noop!(State: ZwpVirtualKeyboardV1);
+
+/// Minimale Keymap mit genau einer Taste für das gewünschte Keysym – wie `wtype` sie erzeugt
+fn keymap_for(keysym: &str) -> String {
+    format!(
+        "xkb_keymap {{\n\
+         xkb_keycodes \"hyprclip\" {{ minimum = 8; maximum = 9; <K1> = 9; }};\n\
+         xkb_types \"hyprclip\" {{ include \"complete\" }};\n\
+         xkb_compatibility \"hyprclip\" {{ include \"complete\" }};\n\
+         xkb_symbols \"hyprclip\" {{ key <K1> {{ [ {keysym} ] }}; }};\n\
+         }};\n"
+    )
+}
+
+/// Sendet den Tastendruck über `zwp_virtual_keyboard_v1`
+pub fn send(keystroke: &Keystroke) -> Result<()> {
+    let conn = Connection::connect_to_env().map_err(wayland_error)?;
+    let (globals, mut queue) = registry_queue_init::<State>(&conn).map_err(wayland_error)?;
+    let qh = queue.handle();
+
+    let seat: WlSeat = globals.bind(&qh, 1..=1, ()).map_err(wayland_error)?;
+    let manager: ZwpVirtualKeyboardManagerV1 = globals
+        .bind(&qh, 1..=1, ())
+        .map_err(|_| Error::Paste("Compositor unterstützt zwp_virtual_keyboard_v1 nicht".into()))?;
+    let keyboard = manager.create_virtual_keyboard(&seat, &qh, ());
+
+    // Keymap als nullterminierten String über eine anonyme Datei übergeben
+    let keymap = keymap_for(&keystroke.keysym);
+    let mut file = tempfile::tempfile()?;
+    file.write_all(keymap.as_bytes())?;
+    file.write_all(&[0])?;
+    keyboard.keymap(KEYMAP_FORMAT_XKB_V1, file.as_fd(), keymap.len() as u32 + 1);
+    queue.roundtrip(&mut State).map_err(wayland_error)?;
+
+    let mask = modifier_mask(keystroke);
+    keyboard.modifiers(mask, 0, 0, 0);
+    keyboard.key(timestamp(), KEYCODE, KEY_PRESSED);
+    keyboard.key(timestamp(), KEYCODE, KEY_RELEASED);
+    keyboard.modifiers(0, 0, 0, 0);
+    queue.roundtrip(&mut State).map_err(wayland_error)?;
+
+    // Dem Compositor kurz Zeit geben, die Events auszuliefern, bevor das Gerät verschwindet
+    std::thread::sleep(Duration::from_millis(20));
+    keyboard.destroy();
+    conn.flush().map_err(wayland_error)?;
+    Ok(())
+}
+
+fn wayland_error(error: impl fmt::Display) -> Error {
+    Error::Paste(format!("Wayland: {error}"))
+}
+
+fn modifier_mask(keystroke: &Keystroke) -> u32 {
+    let mut mask = 0;
+    if keystroke.shift {
+        mask |= MOD_SHIFT;
+    }
+    if keystroke.ctrl {
+        mask |= MOD_CTRL;
+    }
+    if keystroke.alt {
+        mask |= MOD_ALT;
+    }
+    if keystroke.super_key {
+        mask |= MOD_SUPER;
+    }
+    mask
+}
+
+fn timestamp() -> u32 {
+    // Millisekunden mit beliebigem Nullpunkt, wie vom Protokoll erlaubt
+    (chrono::Utc::now().timestamp_millis() & 0xffff_ffff) as u32
+}
@@ -0,0 +1,195 @@
+use crate::{fswatch, history::ClipboardItem};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+use tracing::{error, info};
+
+/// Reihenfolge, in der `hyprclip queue next` die gesammelten Einträge ausgibt
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum QueueOrder {
+    /// Zuerst kopiert, zuerst eingefügt
+    #[default]
+    Fifo,
+    /// Zuletzt kopiert, zuerst eingefügt (Stapel)
+    Lifo,
+}
+
+/// Vorgaben für den Warteschlangen-Modus
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct QueueConfig {
+    pub order: QueueOrder,
+    /// Anzahl zu sammelnder Kopien, wenn `queue start` keine nennt
+    pub size: usize,
+}
+
+impl Default for QueueConfig {
+    fn default() -> Self {
+        Self {
+            order: QueueOrder::Fifo,
+            size: 10,
+        }
+    }
+}
+
+/// Warteschlange zum Einfügen nacheinander; liegt als JSON neben dem Verlauf,
+/// damit Watcher, CLI und Waybar-Modul denselben Stand sehen
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Queue {
+    pub order: QueueOrder,
+    /// Wie viele Kopien noch gesammelt werden
+    pub remaining: usize,
+    /// In Kopier-Reihenfolge
+    pub items: Vec<ClipboardItem>,
+}
+
+impl Queue {
+    pub fn new(size: usize, order: QueueOrder) -> Self {
+        Self {
+            order,
+            remaining: size,
+            items: Vec::new(),
+        }
+    }
+
+    /// Pfad der Zustandsdatei zum jeweiligen Verlauf
+    pub fn path(storage_path: &Path) -> PathBuf {
+        storage_path.with_file_name("queue.json")
+    }
+
+    /// `None`, wenn der Modus nicht aktiv ist
+    pub fn load(path: &Path) -> Option<Self> {
+        let contents = fs::read_to_string(path).ok()?;
+        serde_json::from_str(&contents).ok()
+    }
+
+    /// Eine leere, fertig gesammelte Warteschlange beendet den Modus
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if !self.is_active() {
+            return Self::remove(path);
+        }
+        fswatch::write_atomic(path, serde_json::to_string_pretty(self)?.as_bytes())
+    }
+
+    pub fn remove(path: &Path) -> io::Result<()> {
+        match fs::remove_file(path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn is_collecting(&self) -> bool {
+        self.remaining > 0
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.is_collecting() || !self.items.is_empty()
+    }
+
+    /// Anzahl noch einzufügender Einträge
+    pub fn depth(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Nimmt eine Kopie auf, solange noch gesammelt wird
+    pub fn collect(&mut self, item: ClipboardItem) -> bool {
+        if !self.is_collecting() {
+            return false;
+        }
+        self.items.push(item);
+        self.remaining -= 1;
+        true
+    }
+
+    /// Beendet das Sammeln und gibt den nächsten Eintrag heraus
+    pub fn next(&mut self) -> Option<ClipboardItem> {
+        self.remaining = 0;
+        if self.items.is_empty() {
+            return None;
+        }
+        match self.order {
+            QueueOrder::Fifo => Some(self.items.remove(0)),
+            QueueOrder::Lifo => self.items.pop(),
+        }
+    }
+}
+
+/// Nimmt eine neue Kopie in die Warteschlange auf, falls der Modus gerade sammelt
+pub fn collect(path: &Path, item: &ClipboardItem) {
+    let Some(mut queue) = Queue::load(path) else {
+        return;
+    };
+    if queue.collect(item.clone()) {
+        info!(
+            depth = queue.depth(),
+            remaining = queue.remaining,
+            "Kopie in Warteschlange aufgenommen"
+        );
+        if let Err(e) = queue.save(path) {
+            error!("Warteschlange konnte nicht gespeichert werden: {e}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(s: &str) -> ClipboardItem {
+        ClipboardItem::Text(s.into())
+    }
+
+    fn texts(queue: &mut Queue) -> Vec<String> {
+        std::iter::from_fn(|| queue.next())
+            .map(|item| match item {
+                ClipboardItem::Text(t) => t,
+                ClipboardItem::Image(p) => p.display().to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn collects_up_to_size_in_configured_order() {
+        let mut fifo = Queue::new(2, QueueOrder::Fifo);
+        assert!(fifo.collect(text("a")));
+        assert!(fifo.collect(text("b")));
+        assert!(!fifo.collect(text("c")));
+        let mut lifo = Queue {
+            order: QueueOrder::Lifo,
+            ..fifo.clone()
+        };
+        assert_eq!(texts(&mut fifo), ["a", "b"]);
+        assert_eq!(texts(&mut lifo), ["b", "a"]);
+        assert!(!fifo.is_active());
+    }
+
+    #[test]
+    fn next_stops_collecting() {
+        let mut queue = Queue::new(5, QueueOrder::Fifo);
+        queue.collect(text("a"));
+        queue.collect(text("b"));
+        assert!(queue.next().is_some());
+        assert!(!queue.collect(text("c")));
+        assert_eq!(queue.depth(), 1);
+    }
+
+    #[test]
+    fn file_disappears_when_done() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = Queue::path(&dir.path().join("clipboard.json"));
+
+        Queue::new(1, QueueOrder::Fifo).save(&path).unwrap();
+        collect(&path, &text("a"));
+        let mut queue = Queue::load(&path).unwrap();
+        assert_eq!(queue.depth(), 1);
+        assert!(!queue.is_collecting());
+
+        queue.next();
+        queue.save(&path).unwrap();
+        assert!(!path.exists());
+        assert!(Queue::load(&path).is_none());
+    }
+}
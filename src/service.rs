@@ -0,0 +1,178 @@
+use crate::error::{Error, Result};
+use sd_notify::NotifyState;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process::Command,
+    time::{Duration, Instant},
+};
+use tracing::{debug, warn};
+
+/// Name der User-Unit
+pub const UNIT_NAME: &str = "hyprclip-watcher.service";
+/// Mitgelieferte Unit; `ExecStart` zeigt beim Installieren auf das laufende Programm
+const UNIT_TEMPLATE: &str = include_str!("../systemd/hyprclip-watcher.service");
+
+/// `~/.config/systemd/user/hyprclip-watcher.service`
+pub fn unit_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("systemd")
+        .join("user")
+        .join(UNIT_NAME)
+}
+
+/// Unit-Datei für das Programm unter `exe`
+fn unit_contents(exe: &Path) -> String {
+    UNIT_TEMPLATE
+        .lines()
+        .map(|line| {
+            if line.starts_with("ExecStart=") {
+                format!("ExecStart={} --watch", exe.display())
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        + "\n"
+}
+
+/// Schreibt die Unit und startet den Watcher mit der grafischen Sitzung
+pub fn install() -> Result<()> {
+    let path = unit_path();
+    let exe = std::env::current_exe()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(Error::storage(parent))?;
+    }
+    fs::write(&path, unit_contents(&exe)).map_err(Error::storage(&path))?;
+    println!("🛠️ Unit geschrieben: {}", path.display());
+
+    systemctl(&["daemon-reload"])?;
+    systemctl(&["enable", "--now", UNIT_NAME])?;
+    println!("▶️ {UNIT_NAME} aktiviert und gestartet");
+    Ok(())
+}
+
+/// Stoppt den Watcher und entfernt die Unit
+pub fn uninstall() -> Result<()> {
+    let path = unit_path();
+    if !path.exists() {
+        return Err(Error::NotFound(format!(
+            "{UNIT_NAME} ist nicht installiert ({})",
+            path.display()
+        )));
+    }
+    // Eine nie geladene Unit lässt sich nicht deaktivieren – entfernt wird sie trotzdem
+    if let Err(e) = systemctl(&["disable", "--now", UNIT_NAME]) {
+        warn!("{e}");
+    }
+    fs::remove_file(&path).map_err(Error::storage(&path))?;
+    systemctl(&["daemon-reload"])?;
+    println!("🧹 {UNIT_NAME} entfernt");
+    Ok(())
+}
+
+/// Zeigt, ob die Unit installiert ist, und den Zustand laut systemd
+pub fn status() -> Result<()> {
+    let path = unit_path();
+    if path.exists() {
+        println!("🛠️ Installiert: {}", path.display());
+    } else {
+        println!("🛠️ Nicht installiert (hyprclip service install)");
+        return Ok(());
+    }
+    // Exit-Code ≠ 0 heißt hier nur „nicht aktiv“ – die Ausgabe sagt mehr
+    Command::new("systemctl")
+        .args(["--user", "--no-pager", "status", UNIT_NAME])
+        .status()
+        .map_err(systemctl_missing)?;
+    Ok(())
+}
+
+fn systemctl(args: &[&str]) -> Result<()> {
+    let status = Command::new("systemctl")
+        .arg("--user")
+        .args(args)
+        .status()
+        .map_err(systemctl_missing)?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(Error::Command(format!(
+            "systemctl --user {} beendet mit {status}",
+            args.join(" ")
+        )))
+    }
+}
+
+fn systemctl_missing(e: io::Error) -> Error {
+    Error::Command(format!("systemctl konnte nicht gestartet werden: {e}"))
+}
+
+/// Meldet systemd (`Type=notify`) die Bereitschaft; ohne systemd wirkungslos
+pub fn notify_ready() {
+    notify(&[
+        NotifyState::Ready,
+        NotifyState::Status("Überwacht das Clipboard"),
+    ]);
+}
+
+pub fn notify_stopping() {
+    notify(&[NotifyState::Stopping]);
+}
+
+fn notify(state: &[NotifyState]) {
+    if let Err(e) = sd_notify::notify(false, state) {
+        warn!("systemd-Benachrichtigung fehlgeschlagen: {e}");
+    }
+}
+
+/// Hält den systemd-Watchdog (`WatchdogSec`) am Leben, solange die Watcher-Schleife läuft
+pub struct Watchdog {
+    interval: Option<Duration>,
+    last: Option<Instant>,
+}
+
+impl Watchdog {
+    /// Aktiv nur, wenn systemd `WATCHDOG_USEC` gesetzt hat
+    pub fn from_env() -> Self {
+        let mut usec = 0;
+        let interval = sd_notify::watchdog_enabled(false, &mut usec)
+            // Halbes Intervall, damit eine etwas langsamere Runde nicht sofort zum Neustart führt
+            .then(|| Duration::from_micros(usec) / 2);
+        if let Some(interval) = interval {
+            debug!(?interval, "systemd-Watchdog aktiv");
+        }
+        Self {
+            interval,
+            last: None,
+        }
+    }
+
+    /// Aus jeder Schleifenrunde aufrufen; sendet höchstens einmal pro Intervall
+    pub fn ping(&mut self) {
+        let Some(interval) = self.interval else {
+            return;
+        };
+        if self.last.is_some_and(|last| last.elapsed() < interval) {
+            return;
+        }
+        notify(&[NotifyState::Watchdog]);
+        self.last = Some(Instant::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_runs_current_binary_in_graphical_session() {
+        let unit = unit_contents(Path::new("/opt/hyprclip/bin/hyprclip"));
+        assert!(unit.contains("\nExecStart=/opt/hyprclip/bin/hyprclip --watch\n"));
+        assert!(unit.contains("Type=notify"));
+        assert!(unit.contains("WantedBy=graphical-session.target"));
+        assert!(!unit.contains("/usr/bin/hyprclip"));
+    }
+}
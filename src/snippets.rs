@@ -0,0 +1,313 @@
+use crate::error::{Error, Result};
+use chrono::{
+    format::{Item, StrftimeItems},
+    Local,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs, path::Path, path::PathBuf};
+
+/// Vorgabe für `{date}` ohne eigenes Format
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Textbaustein aus `snippets.toml`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Snippet {
+    /// Eindeutiger Name für CLI und Suche
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Text mit Platzhaltern wie `{date:%d.%m.%Y}`, `{clipboard}`, `{uuid}` oder `{input:Name}`
+    pub content: String,
+}
+
+/// Alle Snippets; in der Datei als `[[snippet]]`-Tabellen
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SnippetStore {
+    #[serde(default, rename = "snippet")]
+    pub snippets: Vec<Snippet>,
+}
+
+/// Ein Stück Vorlage: fester Text oder Platzhalter
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part<'a> {
+    Text(&'a str),
+    Date(&'a str),
+    Clipboard,
+    Uuid,
+    Input(&'a str),
+}
+
+impl SnippetStore {
+    /// Liegt neben der `config.toml`
+    pub fn path() -> PathBuf {
+        dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("hyprclip")
+            .join("snippets.toml")
+    }
+
+    /// Fehlt die Datei, gibt es einfach keine Snippets
+    pub fn load() -> Result<Self> {
+        Self::load_from(&Self::path())
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(path).map_err(Error::storage(path))?;
+        toml::from_str(&contents).map_err(|e| Error::Config(format!("{}: {e}", path.display())))
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Snippet> {
+        self.snippets.iter().find(|s| s.name == name)
+    }
+
+    /// Indizes der Snippets, die zur Suche passen
+    pub fn filter(&self, query: &str) -> Vec<usize> {
+        self.snippets
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| s.matches(query))
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+impl Snippet {
+    /// Jedes Wort muss vorkommen; `#tag` verlangt ein passendes Tag
+    pub fn matches(&self, query: &str) -> bool {
+        query.split_whitespace().all(|word| {
+            let word = word.to_lowercase();
+            match word.strip_prefix('#') {
+                Some(tag) => self.tags.iter().any(|t| t.to_lowercase() == tag),
+                None => {
+                    self.name.to_lowercase().contains(&word)
+                        || self.description.to_lowercase().contains(&word)
+                        || self.tags.iter().any(|t| t.to_lowercase().contains(&word))
+                }
+            }
+        })
+    }
+
+    /// Namen der `{input:…}`-Platzhalter in Reihenfolge, ohne Doppelte
+    pub fn inputs(&self) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for part in parse(&self.content) {
+            if let Part::Input(name) = part {
+                if !names.iter().any(|n| n == name) {
+                    names.push(name.to_string());
+                }
+            }
+        }
+        names
+    }
+
+    /// Setzt alle Platzhalter ein; `clipboard` wird nur bei Bedarf (und nur einmal) abgefragt
+    pub fn expand(
+        &self,
+        inputs: &HashMap<String, String>,
+        clipboard: impl FnOnce() -> Option<String>,
+    ) -> Result<String> {
+        let mut clipboard = Some(clipboard);
+        let mut clipboard_text: Option<String> = None;
+        let mut out = String::with_capacity(self.content.len());
+
+        for part in parse(&self.content) {
+            match part {
+                Part::Text(text) => out.push_str(text),
+                Part::Date(format) => out.push_str(&format_date(format)?),
+                Part::Uuid => out.push_str(&uuid::Uuid::new_v4().to_string()),
+                Part::Input(name) => out.push_str(
+                    inputs
+                        .get(name)
+                        .ok_or_else(|| Error::Invalid(format!("Keine Eingabe für „{name}“")))?,
+                ),
+                Part::Clipboard => {
+                    if let Some(fetch) = clipboard.take() {
+                        clipboard_text = Some(fetch().unwrap_or_default());
+                    }
+                    out.push_str(clipboard_text.as_deref().unwrap_or_default());
+                }
+            }
+        }
+        Ok(out)
+    }
+}
+
+/// Unbekannte oder nicht geschlossene Klammern bleiben als Text stehen. Nach einer
+/// Klammer ohne Platzhalter wird beim nächsten Zeichen weitergesucht, damit Code und JSON
+/// (`{"id": "{uuid}"}`) ihre Platzhalter behalten.
+fn parse(template: &str) -> Vec<Part<'_>> {
+    let mut parts = Vec::new();
+    let mut text_start = 0;
+    let mut pos = 0;
+
+    while let Some(offset) = template[pos..].find('{') {
+        let start = pos + offset;
+        let Some(len) = template[start..].find('}') else {
+            break;
+        };
+        let inner = &template[start + 1..start + len];
+        let (name, arg) = match inner.split_once(':') {
+            Some((name, arg)) => (name, Some(arg)),
+            None => (inner, None),
+        };
+        let placeholder = match (name, arg) {
+            ("date", format) => Some(Part::Date(format.unwrap_or(DEFAULT_DATE_FORMAT))),
+            ("clipboard", None) => Some(Part::Clipboard),
+            ("uuid", None) => Some(Part::Uuid),
+            ("input", Some(name)) if !name.is_empty() => Some(Part::Input(name)),
+            _ => None,
+        };
+
+        match placeholder {
+            Some(part) => {
+                if start > text_start {
+                    parts.push(Part::Text(&template[text_start..start]));
+                }
+                parts.push(part);
+                pos = start + len + 1;
+                text_start = pos;
+            }
+            None => pos = start + 1,
+        }
+    }
+
+    if text_start < template.len() {
+        parts.push(Part::Text(&template[text_start..]));
+    }
+    parts
+}
+
+/// Ungültige Formate würden beim Formatieren panicken, daher vorher prüfen
+fn format_date(format: &str) -> Result<String> {
+    if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+        return Err(Error::Invalid(format!(
+            "Ungültiges Datumsformat „{format}“"
+        )));
+    }
+    Ok(Local::now().format(format).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snippet(content: &str) -> Snippet {
+        Snippet {
+            name: "test".into(),
+            description: String::new(),
+            tags: vec!["mail".into()],
+            content: content.into(),
+        }
+    }
+
+    #[test]
+    fn parses_placeholders_and_keeps_unknown_braces() {
+        assert_eq!(
+            parse("Hi {input:Name}, {date} {x} {date:%H:%M} {"),
+            vec![
+                Part::Text("Hi "),
+                Part::Input("Name"),
+                Part::Text(", "),
+                Part::Date(DEFAULT_DATE_FORMAT),
+                Part::Text(" {x} "),
+                Part::Date("%H:%M"),
+                Part::Text(" {"),
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_placeholders_inside_code_and_json_braces() {
+        assert_eq!(
+            parse("fn main() { println!(\"{clipboard}\"); }"),
+            vec![
+                Part::Text("fn main() { println!(\""),
+                Part::Clipboard,
+                Part::Text("\"); }"),
+            ]
+        );
+
+        let text = snippet("{\"id\": \"{uuid}\", \"n\": {input:N}}")
+            .expand(&HashMap::from([("N".to_string(), "1".to_string())]), || {
+                None
+            })
+            .unwrap();
+        let id = text
+            .strip_prefix("{\"id\": \"")
+            .and_then(|rest| rest.strip_suffix("\", \"n\": 1}"))
+            .unwrap();
+        assert!(uuid::Uuid::parse_str(id).is_ok(), "{text}");
+    }
+
+    #[test]
+    fn expands_inputs_clipboard_and_uuid() {
+        let s = snippet("{input:Name}/{input:Ort}/{input:Name} {clipboard}{clipboard} {uuid}");
+        assert_eq!(s.inputs(), vec!["Name", "Ort"]);
+
+        let inputs = HashMap::from([
+            ("Name".to_string(), "Ada".to_string()),
+            ("Ort".to_string(), "London".to_string()),
+        ]);
+        let mut calls = 0;
+        let text = s
+            .expand(&inputs, || {
+                calls += 1;
+                Some("x".into())
+            })
+            .unwrap();
+        assert_eq!(calls, 1);
+
+        let (fixed, uuid) = text.rsplit_once(' ').unwrap();
+        assert_eq!(fixed, "Ada/London/Ada xx");
+        assert!(uuid::Uuid::parse_str(uuid).is_ok());
+
+        assert!(s.expand(&HashMap::new(), || None).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_date_format() {
+        assert!(snippet("{date:%Q}")
+            .expand(&HashMap::new(), || None)
+            .is_err());
+        let year = Local::now().format("%Y").to_string();
+        assert_eq!(
+            snippet("{date:%Y}")
+                .expand(&HashMap::new(), || None)
+                .unwrap(),
+            year
+        );
+    }
+
+    #[test]
+    fn loads_store_and_filters_by_tag() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snippets.toml");
+        assert!(SnippetStore::load_from(&path).unwrap().snippets.is_empty());
+
+        fs::write(
+            &path,
+            r#"
+[[snippet]]
+name = "gruss"
+tags = ["mail"]
+content = "Viele Grüße"
+
+[[snippet]]
+name = "ticket"
+description = "Ticket-Vorlage"
+content = "Ticket {uuid}"
+"#,
+        )
+        .unwrap();
+        let store = SnippetStore::load_from(&path).unwrap();
+        assert_eq!(store.get("ticket").unwrap().description, "Ticket-Vorlage");
+        assert_eq!(store.filter("#mail"), vec![0]);
+        assert_eq!(store.filter("vorlage"), vec![1]);
+        assert_eq!(store.filter(""), vec![0, 1]);
+    }
+}
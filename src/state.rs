@@ -0,0 +1,67 @@
+use crate::fswatch;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Laufzeitzustand des Watchers; liegt als JSON neben dem Verlauf, damit CLI,
+/// GUI und Statusmodule ihn lesen bzw. ändern können
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WatcherState {
+    /// Neue Kopien werden nicht gespeichert
+    pub paused: bool,
+    /// Der aktuelle Clipboard-Inhalt ist als vertraulich markiert und wurde nicht gespeichert
+    pub sensitive: bool,
+}
+
+impl WatcherState {
+    /// Pfad der Zustandsdatei zum jeweiligen Verlauf
+    pub fn path(storage_path: &Path) -> PathBuf {
+        storage_path.with_file_name("state.json")
+    }
+
+    /// Fehlende oder kaputte Datei = Standardzustand
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fswatch::write_atomic(path, serde_json::to_string_pretty(self)?.as_bytes())
+    }
+
+    /// Ändert den Zustand und schreibt nur, wenn sich wirklich etwas geändert hat
+    /// (jede Änderung weckt die Statusmodule)
+    pub fn update(path: &Path, change: impl FnOnce(&mut Self)) -> io::Result<Self> {
+        let old = Self::load(path);
+        let mut new = old.clone();
+        change(&mut new);
+        if new != old {
+            new.save(path)?;
+        }
+        Ok(new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn update_writes_only_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = WatcherState::path(&dir.path().join("clipboard.json"));
+        assert_eq!(WatcherState::load(&path), WatcherState::default());
+
+        WatcherState::update(&path, |s| s.sensitive = false).unwrap();
+        assert!(!path.exists());
+
+        let state = WatcherState::update(&path, |s| s.paused = true).unwrap();
+        assert!(state.paused);
+        assert_eq!(WatcherState::load(&path), state);
+    }
+}
@@ -0,0 +1,21 @@
+use super::{plain, Status};
+use serde_json::{json, Value};
+
+/// Flaches JSON für `deflisten` (eww) bzw. ein Skript-Modul (ironbar); ohne Markup
+pub fn render(status: &Status) -> Value {
+    let latest = status.latest();
+    json!({
+        "text": status.text(plain),
+        "count": status.count(),
+        "limit": status.history.limit,
+        "percentage": status.percentage(),
+        "pinned": status.history.entries.iter().filter(|e| e.pinned).count(),
+        "queue": status.queue.as_ref().map(|q| q.depth()),
+        "paused": status.state.paused,
+        "sensitive": status.state.sensitive,
+        "latest": latest.map(|e| e.summary(status.cfg.max_chars)),
+        "latest_type": latest.map(|e| e.kind.name()),
+        "class": status.classes().join(" "),
+        "tooltip": status.tooltip_lines(plain).join("\n"),
+    })
+}
@@ -0,0 +1,27 @@
+use super::{plain, Status};
+use serde_json::{json, Value};
+
+/// Block nach dem i3bar-Protokoll; i3blocks liest ihn mit `format=json`, für i3bar
+/// kommt er in eine Statuszeile (siehe [`super::StatusFormat::I3bar`])
+pub fn render(status: &Status) -> Value {
+    let classes = status.classes();
+    let color = if status.state.paused {
+        Some("#888888")
+    } else if status.state.sensitive {
+        Some("#e5c07b")
+    } else {
+        None
+    };
+
+    let mut block = json!({
+        "name": "hyprclip",
+        "instance": classes.first().copied().unwrap_or("default"),
+        "full_text": status.text(plain),
+        "short_text": status.count().to_string(),
+        "percentage": status.percentage(),
+    });
+    if let Some(color) = color {
+        block["color"] = json!(color);
+    }
+    block
+}
@@ -0,0 +1,380 @@
+mod eww;
+mod i3blocks;
+mod polybar;
+mod waybar;
+
+use crate::{
+    config::Config,
+    error::Result,
+    fswatch::FileWatcher,
+    history::{Entry, History},
+    kind::ContentKind,
+    queue::Queue,
+    state::WatcherState,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    io::{self, Write},
+    time::Duration,
+};
+use tokio::sync::mpsc;
+
+/// Wartezeit nach einer Änderung, damit halb geschriebene Dateien nicht gelesen werden
+/// und mehrere Schreibvorgänge nur eine Ausgabe erzeugen
+const SETTLE_DELAY: Duration = Duration::from_millis(100);
+
+/// Kopfzeile des i3bar-Protokolls und Beginn des endlosen Arrays aus Statuszeilen
+const I3BAR_HEADER: &str = "{\"version\":1}\n[";
+
+/// Ausgabeformat für Statusleisten
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum StatusFormat {
+    /// JSON für Waybar (`return-type: json`)
+    Waybar,
+    /// Block-JSON für i3blocks (`format=json`)
+    I3blocks,
+    /// i3bar-Protokoll (Kopfzeile und endloses Array) für `status_command` von i3/sway
+    I3bar,
+    /// Text mit Klick-Aktionen für polybar
+    Polybar,
+    /// JSON-Variablen für eww und ironbar
+    Eww,
+}
+
+/// Darstellung der Statusmodule; gilt für alle Formate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StatusConfig {
+    /// Text mit Platzhaltern `{count}`, `{latest}`, `{latest_type}`, `{pinned}`, `{queue}`
+    pub format: String,
+    /// Text, solange der Warteschlangen-Modus aktiv ist
+    pub format_queue: String,
+    /// Text, solange die Aufzeichnung pausiert ist
+    pub format_paused: String,
+    /// Anzahl der neuesten Einträge im Tooltip
+    pub tooltip_entries: usize,
+    /// Maximale Zeichen für `{latest}` und je Tooltip-Zeile
+    pub max_chars: usize,
+    /// Befehl bei Linksklick (polybar)
+    pub on_click: String,
+    /// Befehl bei Rechtsklick (polybar); leer = keiner
+    pub on_click_right: String,
+}
+
+impl Default for StatusConfig {
+    fn default() -> Self {
+        Self {
+            format: "📋".to_string(),
+            format_queue: "📚 {queue}".to_string(),
+            format_paused: "⏸️".to_string(),
+            tooltip_entries: 10,
+            max_chars: 50,
+            on_click: "hyprclip --gui".to_string(),
+            on_click_right: String::new(),
+        }
+    }
+}
+
+/// Gemeinsames Datenmodell aller Ausgabeformate
+pub struct Status<'a> {
+    pub history: History,
+    pub state: WatcherState,
+    pub queue: Option<Queue>,
+    pub cfg: &'a StatusConfig,
+}
+
+impl<'a> Status<'a> {
+    pub fn load(cfg: &'a Config) -> Self {
+        Self {
+            history: History::load(&cfg.storage_path, cfg.history_limit),
+            state: WatcherState::load(&WatcherState::path(&cfg.storage_path)),
+            queue: Queue::load(&Queue::path(&cfg.storage_path)),
+            cfg: &cfg.status,
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.history.entries.len()
+    }
+
+    pub fn latest(&self) -> Option<&Entry> {
+        self.history.entries.first()
+    }
+
+    /// Füllstand gemessen an `history_limit` (0–100)
+    pub fn percentage(&self) -> usize {
+        (self.count() * 100)
+            .checked_div(self.history.limit)
+            .unwrap_or_default()
+            .min(100)
+    }
+
+    /// Ausgefüllte Vorlage passend zum Zustand; `escape` gilt für eingesetzte Inhalte
+    pub fn text(&self, escape: fn(&str) -> String) -> String {
+        let template = if self.state.paused {
+            &self.cfg.format_paused
+        } else if self.queue.is_some() {
+            &self.cfg.format_queue
+        } else {
+            &self.cfg.format
+        };
+        let latest = self.latest();
+        fill(
+            template,
+            &[
+                ("count", self.count().to_string()),
+                (
+                    "pinned",
+                    self.history
+                        .entries
+                        .iter()
+                        .filter(|e| e.pinned)
+                        .count()
+                        .to_string(),
+                ),
+                (
+                    "queue",
+                    self.queue.as_ref().map_or(0, Queue::depth).to_string(),
+                ),
+                (
+                    "latest",
+                    latest.map_or_else(String::new, |e| escape(&e.summary(self.cfg.max_chars))),
+                ),
+                (
+                    "latest_type",
+                    latest.map_or("", |e| e.kind.name()).to_string(),
+                ),
+            ],
+        )
+    }
+
+    /// Zustandsklassen, z. B. für CSS
+    pub fn classes(&self) -> Vec<&'static str> {
+        let mut classes = Vec::new();
+        if self.count() == 0 {
+            classes.push("empty");
+        }
+        if self.state.paused {
+            classes.push("paused");
+        }
+        if self.queue.is_some() {
+            classes.push("queue");
+        }
+        if self.state.sensitive {
+            classes.push("sensitive-latest");
+        }
+        if self.latest().is_some_and(|e| e.kind == ContentKind::Image) {
+            classes.push("image-latest");
+        }
+        classes
+    }
+
+    /// Überschrift mit Füllstand, Zustand und die neuesten Einträge
+    pub fn tooltip_lines(&self, escape: fn(&str) -> String) -> Vec<String> {
+        let mut lines = vec![format!(
+            "Hyprclip – {}/{} Einträge",
+            self.count(),
+            self.history.limit
+        )];
+        if self.state.paused {
+            lines.push("⏸️ Aufzeichnung pausiert".to_string());
+        }
+        if self.state.sensitive {
+            lines.push("🔒 Vertraulicher Inhalt nicht gespeichert".to_string());
+        }
+        if let Some(queue) = &self.queue {
+            lines.push(format!("📚 Warteschlange: {}", queue.depth()));
+        }
+        lines.extend(
+            self.history
+                .entries
+                .iter()
+                .take(self.cfg.tooltip_entries)
+                .map(|entry| {
+                    let pin = if entry.pinned { "📌 " } else { "" };
+                    format!(
+                        "{pin}{} {}",
+                        entry.kind.badge(),
+                        escape(&entry.summary(self.cfg.max_chars))
+                    )
+                }),
+        );
+        lines
+    }
+}
+
+/// Gibt den Status einmal aus; mit `follow` bei jeder Änderung erneut (eine Zeile je Zustand)
+pub async fn run(cfg: &Config, format: StatusFormat, follow: bool) -> Result<()> {
+    if format == StatusFormat::I3bar {
+        println!("{I3BAR_HEADER}");
+    }
+    if !follow {
+        println!("{}", render(format, &Status::load(cfg)));
+        return Ok(());
+    }
+
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let queue_path = Queue::path(&cfg.storage_path);
+    let state_path = WatcherState::path(&cfg.storage_path);
+    let _watchers = [&cfg.storage_path, &queue_path, &state_path]
+        .into_iter()
+        .map(|path| {
+            let tx = tx.clone();
+            FileWatcher::new(path, move || {
+                let _ = tx.send(());
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut last = String::new();
+    loop {
+        let line = render(format, &Status::load(cfg));
+        // Leisten lesen zeilenweise; unveränderte Zustände nicht erneut senden
+        if line != last {
+            // Im i3bar-Array trennt ein Komma jede weitere Statuszeile von der vorigen
+            let separator = if format == StatusFormat::I3bar && !last.is_empty() {
+                ","
+            } else {
+                ""
+            };
+            let mut stdout = io::stdout().lock();
+            writeln!(stdout, "{separator}{line}")?;
+            stdout.flush()?;
+            last = line;
+        }
+
+        if rx.recv().await.is_none() {
+            return Ok(());
+        }
+        tokio::time::sleep(SETTLE_DELAY).await;
+        while rx.try_recv().is_ok() {}
+    }
+}
+
+/// Eine Zeile im gewählten Format
+fn render(format: StatusFormat, status: &Status) -> String {
+    match format {
+        StatusFormat::Waybar => waybar::render(status).to_string(),
+        StatusFormat::I3blocks => i3blocks::render(status).to_string(),
+        StatusFormat::I3bar => serde_json::Value::Array(vec![i3blocks::render(status)]).to_string(),
+        StatusFormat::Polybar => polybar::render(status),
+        StatusFormat::Eww => eww::render(status).to_string(),
+    }
+}
+
+/// Ersetzt `{name}` durch den jeweiligen Wert; unbekannte Platzhalter bleiben stehen.
+/// Ein Durchlauf über die Vorlage – eingesetzte Werte (z. B. Clipboard-Text mit
+/// `{latest_type}`) werden nicht noch einmal ersetzt.
+fn fill(template: &str, values: &[(&str, String)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let value = rest.find('}').and_then(|end| {
+            let name = &rest[1..end];
+            values
+                .iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, value)| (value, end))
+        });
+        match value {
+            Some((value, end)) => {
+                out.push_str(value);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Für Formate ohne Markup
+fn plain(text: &str) -> String {
+    text.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub(super) fn status(history: History, state: WatcherState, cfg: &StatusConfig) -> Status<'_> {
+        Status {
+            history,
+            state,
+            queue: None,
+            cfg,
+        }
+    }
+
+    #[test]
+    fn fills_template_for_current_state() {
+        let mut history = History::new(4);
+        history.add_text("hallo".into());
+        history.toggle_pin(0);
+        let cfg = StatusConfig {
+            format: "{count}/{pinned} {latest_type}: {latest} {unknown}".into(),
+            ..StatusConfig::default()
+        };
+
+        let mut s = status(history, WatcherState::default(), &cfg);
+        assert_eq!(s.text(plain), "1/1 text: hallo {unknown}");
+        assert_eq!(s.percentage(), 25);
+        assert!(s.classes().is_empty());
+
+        s.queue = Some(Queue::new(3, crate::queue::QueueOrder::Fifo));
+        assert_eq!(s.text(plain), "📚 0");
+        s.state.paused = true;
+        assert_eq!(s.text(plain), "⏸️");
+        assert_eq!(s.classes(), ["paused", "queue"]);
+    }
+
+    #[test]
+    fn inserted_values_are_not_filled_again() {
+        let values = [
+            ("latest", "{latest_type} {{x}".to_string()),
+            ("latest_type", "url".to_string()),
+        ];
+        assert_eq!(
+            fill("{{latest}} {latest_type} {nope}", &values),
+            "{{latest_type} {{x}} url {nope}"
+        );
+    }
+
+    #[test]
+    fn classes_reflect_state() {
+        let cfg = StatusConfig::default();
+        let state = WatcherState {
+            paused: false,
+            sensitive: true,
+        };
+        assert_eq!(
+            status(History::new(10), state, &cfg).classes(),
+            ["empty", "sensitive-latest"]
+        );
+
+        let mut history = History::new(10);
+        history.add_image("/tmp/hyprclip_status.png".into(), 1);
+        assert_eq!(
+            status(history, WatcherState::default(), &cfg).classes(),
+            ["image-latest"]
+        );
+    }
+
+    #[test]
+    fn i3bar_lines_are_arrays_of_blocks() {
+        let cfg = StatusConfig::default();
+        let line = render(
+            StatusFormat::I3bar,
+            &status(History::new(10), WatcherState::default(), &cfg),
+        );
+        let blocks: Vec<serde_json::Value> = serde_json::from_str(&line).unwrap();
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0]["name"], "hyprclip");
+        assert!(I3BAR_HEADER.starts_with("{\"version\":1}"));
+    }
+}
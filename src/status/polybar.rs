@@ -0,0 +1,44 @@
+use super::Status;
+
+/// Klartext für `custom/script` mit `tail = true`; Klicks als Aktions-Tags
+pub fn render(status: &Status) -> String {
+    let mut text = status.text(escape);
+    for (button, command) in [(1, &status.cfg.on_click), (3, &status.cfg.on_click_right)] {
+        if !command.is_empty() {
+            text = format!("%{{A{button}:{}:}}{text}%{{A}}", escape_action(command));
+        }
+    }
+    text
+}
+
+/// `%{` leitet bei polybar Formatierungs-Tags ein; ein unsichtbares Leerzeichen entschärft es
+fn escape(text: &str) -> String {
+    text.replace("%{", "%\u{200b}{")
+}
+
+/// Doppelpunkte beenden den Befehl im Aktions-Tag
+fn escape_action(command: &str) -> String {
+    command.replace(':', "\\:")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{history::History, state::WatcherState, status::StatusConfig};
+
+    #[test]
+    fn wraps_text_in_action_tags() {
+        let mut history = History::new(4);
+        history.add_text("%{F#f00}".into());
+        let cfg = StatusConfig {
+            format: "{latest}".into(),
+            on_click_right: "notify-send a:b".into(),
+            ..StatusConfig::default()
+        };
+        let status = super::super::tests::status(history, WatcherState::default(), &cfg);
+        assert_eq!(
+            render(&status),
+            "%{A3:notify-send a\\:b:}%{A1:hyprclip --gui:}%\u{200b}{F#f00}%{A}%{A}"
+        );
+    }
+}
@@ -0,0 +1,51 @@
+use super::Status;
+use serde_json::{json, Value};
+
+/// Waybar erwartet `return-type: json`; Text und Tooltip sind Pango-Markup
+pub fn render(status: &Status) -> Value {
+    let classes = status.classes();
+    let mut tooltip = status.tooltip_lines(escape_pango);
+    tooltip[0] = format!("<b>{}</b>", tooltip[0]);
+
+    json!({
+        "text": status.text(escape_pango),
+        "alt": classes.first().copied().unwrap_or("default"),
+        "tooltip": tooltip.join("\n"),
+        "class": classes,
+        "percentage": status.percentage(),
+    })
+}
+
+fn escape_pango(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{history::History, state::WatcherState, status::StatusConfig};
+
+    #[test]
+    fn escapes_markup() {
+        let mut history = History::new(4);
+        history.add_text("a < b & c".into());
+        let cfg = StatusConfig {
+            format: "{latest}".into(),
+            ..StatusConfig::default()
+        };
+        let status = super::super::tests::status(history, WatcherState::default(), &cfg);
+
+        let out = render(&status);
+        assert_eq!(out["text"], "a &lt; b &amp; c");
+        assert!(out["tooltip"]
+            .as_str()
+            .unwrap()
+            .ends_with("a &lt; b &amp; c"));
+        assert_eq!(out["percentage"], 25);
+        assert_eq!(out["alt"], "default");
+    }
+}
@@ -0,0 +1,91 @@
+use crate::error::Result;
+use image::DynamicImage;
+use std::path::{Path, PathBuf};
+
+/// Maximale Kantenlänge der Vorschaubilder in Pixeln
+pub const THUMBNAIL_SIZE: u32 = 256;
+
+/// Pfad des Vorschaubildes direkt neben dem Original (`clip_….png` → `clip_….thumb.png`)
+pub fn thumbnail_path(image: &Path) -> PathBuf {
+    let stem = image.file_stem().unwrap_or_default().to_string_lossy();
+    image.with_file_name(format!("{stem}.thumb.png"))
+}
+
+/// Prüft, ob eine Datei selbst ein Vorschaubild ist
+pub fn is_thumbnail(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|n| n.to_string_lossy().ends_with(".thumb.png"))
+}
+
+/// Schreibt das Vorschaubild für ein bereits dekodiertes Bild (Watcher beim Einfügen)
+pub fn write_for(image_path: &Path, image: &DynamicImage) -> Result<PathBuf> {
+    let target = thumbnail_path(image_path);
+    image
+        .thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE)
+        .to_rgba8()
+        .save(&target)?;
+    Ok(target)
+}
+
+/// Gibt das Vorschaubild zurück und erzeugt es bei Bedarf (für ältere Einträge)
+pub fn ensure(image_path: &Path) -> Result<PathBuf> {
+    let target = thumbnail_path(image_path);
+    let up_to_date = match (target.metadata(), image_path.metadata()) {
+        (Ok(thumb), Ok(orig)) => match (thumb.modified(), orig.modified()) {
+            (Ok(t), Ok(o)) => t >= o,
+            _ => true,
+        },
+        _ => false,
+    };
+
+    if up_to_date {
+        return Ok(target);
+    }
+
+    let image = image::open(image_path)?;
+    write_for(image_path, &image)
+}
+
+/// Löscht das Vorschaubild eines Bildes, falls vorhanden
+pub fn remove(image_path: &Path) {
+    let target = thumbnail_path(image_path);
+    if target.exists() {
+        if let Err(e) = std::fs::remove_file(&target) {
+            tracing::warn!(path = %target.display(), "Vorschaubild konnte nicht gelöscht werden: {e}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageBuffer, Rgba};
+
+    #[test]
+    fn thumbnail_path_sits_next_to_image() {
+        let path = PathBuf::from("/data/images/clip_ab_2024.png");
+        let thumb = thumbnail_path(&path);
+        assert_eq!(thumb, PathBuf::from("/data/images/clip_ab_2024.thumb.png"));
+        assert!(is_thumbnail(&thumb));
+        assert!(!is_thumbnail(&path));
+    }
+
+    #[test]
+    fn ensure_generates_scaled_thumbnail() {
+        let dir = tempfile::tempdir().unwrap();
+        let original = dir.path().join("clip_test.png");
+        ImageBuffer::from_pixel(1024, 512, Rgba([255u8, 0, 0, 255]))
+            .save(&original)
+            .unwrap();
+
+        let thumb = ensure(&original).unwrap();
+        let img = image::open(&thumb).unwrap();
+        assert_eq!(
+            (img.width(), img.height()),
+            (THUMBNAIL_SIZE, THUMBNAIL_SIZE / 2)
+        );
+
+        remove(&original);
+        assert!(!thumb.exists());
+    }
+}
@@ -0,0 +1,229 @@
+use super::Transform;
+use crate::error::{Error, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use std::collections::HashSet;
+
+/// Die Funktionen dürfen beliebige Fehler liefern; `apply` macht daraus [`Error::Invalid`]
+type TransformFn = fn(&str) -> Result<String, Box<dyn std::error::Error>>;
+
+/// Eingebaute Umwandlung als einfache Funktion
+pub struct Builtin {
+    name: &'static str,
+    description: &'static str,
+    func: TransformFn,
+}
+
+impl Transform for Builtin {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn description(&self) -> &str {
+        self.description
+    }
+
+    fn apply(&self, input: &str) -> Result<String> {
+        (self.func)(input).map_err(|e| Error::Invalid(format!("{}: {e}", self.name)))
+    }
+}
+
+pub fn all() -> Vec<Builtin> {
+    let table: [(&'static str, &'static str, TransformFn); 18] = [
+        ("trim", "Leerraum am Anfang und Ende entfernen", |s| {
+            Ok(s.trim().to_string())
+        }),
+        ("upper", "GROSSBUCHSTABEN", |s| Ok(s.to_uppercase())),
+        ("lower", "kleinbuchstaben", |s| Ok(s.to_lowercase())),
+        ("title", "Jedes Wort Groß", |s| Ok(title_case(s))),
+        (
+            "strip-formatting",
+            "ANSI-Farben, HTML-Tags und Sonderleerzeichen entfernen",
+            |s| Ok(strip_formatting(s)),
+        ),
+        ("url-encode", "Prozent-Kodierung für URLs", |s| {
+            Ok(utf8_percent_encode(s, URL_ENCODE).to_string())
+        }),
+        ("url-decode", "Prozent-Kodierung auflösen", |s| {
+            Ok(percent_decode_str(&s.replace('+', " "))
+                .decode_utf8()?
+                .into_owned())
+        }),
+        ("base64-encode", "Base64 kodieren", |s| {
+            Ok(STANDARD.encode(s))
+        }),
+        ("base64-decode", "Base64 dekodieren", |s| {
+            let cleaned: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+            Ok(String::from_utf8(STANDARD.decode(cleaned)?)?)
+        }),
+        ("json-pretty", "JSON formatieren", |s| {
+            let value: serde_json::Value = serde_json::from_str(s)?;
+            Ok(serde_json::to_string_pretty(&value)?)
+        }),
+        ("json-minify", "JSON kompakt", |s| {
+            let value: serde_json::Value = serde_json::from_str(s)?;
+            Ok(serde_json::to_string(&value)?)
+        }),
+        ("json-escape", "Als JSON-String maskieren", |s| {
+            let quoted = serde_json::to_string(s)?;
+            Ok(quoted[1..quoted.len() - 1].to_string())
+        }),
+        ("json-unescape", "JSON-String-Maskierung auflösen", |s| {
+            let trimmed = s.trim();
+            let quoted = if trimmed.len() >= 2 && trimmed.starts_with('"') && trimmed.ends_with('"')
+            {
+                trimmed.to_string()
+            } else {
+                format!("\"{s}\"")
+            };
+            Ok(serde_json::from_str::<String>(&quoted)?)
+        }),
+        ("shell-escape", "Für die Shell quoten", |s| {
+            Ok(shlex::try_quote(s)?.into_owned())
+        }),
+        ("shell-unescape", "Shell-Quoting auflösen", |s| {
+            shlex::split(s)
+                .map(|words| words.join(" "))
+                .ok_or_else(|| "ungültiges Shell-Quoting".into())
+        }),
+        ("sort-lines", "Zeilen sortieren", |s| {
+            let mut lines: Vec<&str> = s.lines().collect();
+            lines.sort_unstable();
+            Ok(lines.join("\n"))
+        }),
+        ("dedup-lines", "Doppelte Zeilen entfernen", |s| {
+            let mut seen = HashSet::new();
+            Ok(s.lines()
+                .filter(|l| seen.insert(*l))
+                .collect::<Vec<_>>()
+                .join("\n"))
+        }),
+        ("remove-blank-lines", "Leere Zeilen entfernen", |s| {
+            Ok(s.lines()
+                .filter(|l| !l.trim().is_empty())
+                .collect::<Vec<_>>()
+                .join("\n"))
+        }),
+    ];
+
+    table
+        .into_iter()
+        .map(|(name, description, func)| Builtin {
+            name,
+            description,
+            func,
+        })
+        .collect()
+}
+
+/// Wie `encodeURIComponent`: nur `A-Z a-z 0-9 - _ . ~` bleiben stehen
+const URL_ENCODE: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+fn title_case(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut word_start = true;
+    for c in s.chars() {
+        if c.is_alphanumeric() {
+            if word_start {
+                out.extend(c.to_uppercase());
+            } else {
+                out.extend(c.to_lowercase());
+            }
+            word_start = false;
+        } else {
+            out.push(c);
+            word_start = c.is_whitespace() || c == '-' || c == '_';
+        }
+    }
+    out
+}
+
+/// Entfernt ANSI-Escape-Sequenzen, HTML-Tags und unsichtbare bzw. geschützte Leerzeichen
+fn strip_formatting(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            // CSI-Sequenz: ESC [ … Endbuchstabe
+            '\u{1b}' => {
+                if chars.next_if_eq(&'[').is_some() {
+                    for c in chars.by_ref() {
+                        if c.is_ascii_alphabetic() {
+                            break;
+                        }
+                    }
+                }
+            }
+            '<' if chars
+                .peek()
+                .is_some_and(|n| n.is_ascii_alphabetic() || *n == '/') =>
+            {
+                for c in chars.by_ref() {
+                    if c == '>' {
+                        break;
+                    }
+                }
+            }
+            '\u{a0}' | '\u{202f}' => out.push(' '),
+            '\u{200b}' | '\u{200c}' | '\u{200d}' | '\u{feff}' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(name: &str, input: &str) -> String {
+        let all = all();
+        let t = all.iter().find(|t| t.name == name).unwrap();
+        t.apply(input).unwrap()
+    }
+
+    #[test]
+    fn case_and_line_transforms() {
+        assert_eq!(run("title", "hallo wELT-weit"), "Hallo Welt-Weit");
+        assert_eq!(run("sort-lines", "b\na\nc"), "a\nb\nc");
+        assert_eq!(run("dedup-lines", "a\nb\na"), "a\nb");
+        assert_eq!(run("remove-blank-lines", "a\n\n  \nb"), "a\nb");
+    }
+
+    #[test]
+    fn encodings_roundtrip() {
+        let text = "a b/ä?x=1&y";
+        assert_eq!(run("url-encode", text), "a%20b%2F%C3%A4%3Fx%3D1%26y");
+        assert_eq!(run("url-decode", &run("url-encode", text)), text);
+        assert_eq!(run("base64-decode", &run("base64-encode", text)), text);
+        assert_eq!(
+            run("json-unescape", &run("json-escape", "zeile\n\"zitat\"")),
+            "zeile\n\"zitat\""
+        );
+        assert_eq!(run("shell-escape", "it's"), "\"it's\"");
+        assert_eq!(run("shell-unescape", "'it'\\''s'"), "it's");
+    }
+
+    #[test]
+    fn json_and_formatting() {
+        assert_eq!(run("json-minify", "{ \"a\": [1, 2] }"), "{\"a\":[1,2]}");
+        assert!(run("json-pretty", "{\"a\":1}").contains("\n  \"a\": 1"));
+        assert_eq!(
+            run(
+                "strip-formatting",
+                "\u{1b}[1;31mrot\u{1b}[0m <b>fett</b>\u{a0}x"
+            ),
+            "rot fett x"
+        );
+        assert!(all()
+            .iter()
+            .find(|t| t.name == "json-pretty")
+            .unwrap()
+            .apply("kein json")
+            .is_err());
+    }
+}
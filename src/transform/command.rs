@@ -0,0 +1,115 @@
+use super::Transform;
+use crate::{
+    error::{Error, Result},
+    hooks,
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Eigene Umwandlung aus der Konfiguration: jede Stufe läuft per `sh -c`,
+/// die Ausgabe einer Stufe ist die Eingabe der nächsten
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CommandTransform {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub pipeline: Vec<String>,
+    /// Zeitlimit je Stufe; danach wird der Befehl beendet
+    #[serde(default = "default_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+fn default_timeout_ms() -> u64 {
+    10_000
+}
+
+impl Transform for CommandTransform {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn apply(&self, input: &str) -> Result<String> {
+        let mut text = input.to_string();
+        for stage in &self.pipeline {
+            text = run_stage(stage, &text, Duration::from_millis(self.timeout_ms))?;
+        }
+        Ok(text)
+    }
+}
+
+fn run_stage(command: &str, input: &str, timeout: Duration) -> Result<String> {
+    // Eigener Thread mit eigener Laufzeit: `apply` wird sowohl aus der GUI (ohne Tokio)
+    // als auch aus der asynchronen CLI aufgerufen
+    let output = std::thread::scope(|scope| {
+        scope
+            .spawn(|| {
+                tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .map_err(|e| e.to_string())?
+                    .block_on(hooks::run_command(
+                        command,
+                        Vec::new(),
+                        input.as_bytes(),
+                        timeout,
+                    ))
+            })
+            .join()
+            .unwrap_or_else(|_| Err("abgestürzt".to_string()))
+    })
+    .map_err(|e| Error::Command(format!("„{command}“: {e}")))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(Error::Command(format!(
+            "„{command}“ fehlgeschlagen ({}): {}",
+            output.status,
+            stderr.trim()
+        )));
+    }
+    String::from_utf8(output.stdout)
+        .map_err(|_| Error::Command(format!("„{command}“ lieferte kein gültiges UTF-8")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runs_pipeline_stages_in_order() {
+        let t = CommandTransform {
+            name: "shout".into(),
+            description: String::new(),
+            pipeline: vec!["tr a-z A-Z".into(), "sed 's/$/!/'".into()],
+            timeout_ms: default_timeout_ms(),
+        };
+        assert_eq!(t.apply("hallo\n").unwrap(), "HALLO!\n");
+
+        let failing = CommandTransform {
+            name: "fail".into(),
+            description: String::new(),
+            pipeline: vec!["exit 3".into()],
+            timeout_ms: default_timeout_ms(),
+        };
+        assert!(failing.apply("x").is_err());
+    }
+
+    #[test]
+    fn hanging_stages_are_killed_after_timeout() {
+        let t = CommandTransform {
+            name: "hang".into(),
+            description: String::new(),
+            pipeline: vec!["sleep 5".into()],
+            timeout_ms: 100,
+        };
+        let started = std::time::Instant::now();
+        let err = t.apply("x").unwrap_err();
+        assert!(err.to_string().contains("Zeitlimit"), "{err}");
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+}
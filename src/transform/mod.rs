@@ -0,0 +1,115 @@
+mod builtin;
+mod command;
+
+pub use command::CommandTransform;
+
+use crate::{
+    error::{Error, Result},
+    history::{ClipboardItem, Entry, History},
+};
+use std::sync::Arc;
+
+/// Eine Umwandlung, die aus einem Text einen neuen Text erzeugt
+pub trait Transform: Send + Sync {
+    /// Kennung für CLI und Konfiguration, z. B. `upper` oder `json-pretty`
+    fn name(&self) -> &str;
+    /// Kurze Beschreibung für Listen
+    fn description(&self) -> &str;
+    fn apply(&self, input: &str) -> Result<String>;
+}
+
+/// Alle verfügbaren Umwandlungen: eingebaute plus die aus der Konfiguration
+pub struct Transforms(Vec<Arc<dyn Transform>>);
+
+impl Transforms {
+    /// Eigene Umwandlungen mit gleichem Namen ersetzen eingebaute
+    pub fn new(custom: &[CommandTransform]) -> Self {
+        let mut all: Vec<Arc<dyn Transform>> = builtin::all()
+            .into_iter()
+            .filter(|b| custom.iter().all(|c| c.name != b.name()))
+            .map(|b| Arc::new(b) as Arc<dyn Transform>)
+            .collect();
+        all.extend(
+            custom
+                .iter()
+                .cloned()
+                .map(|c| Arc::new(c) as Arc<dyn Transform>),
+        );
+        Self(all)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn Transform> {
+        self.0.iter().find(|t| t.name() == name).map(|t| t.as_ref())
+    }
+
+    /// Für Umwandlungen in einem eigenen Thread (GUI)
+    pub fn shared(&self, name: &str) -> Option<Arc<dyn Transform>> {
+        self.0.iter().find(|t| t.name() == name).cloned()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Transform> {
+        self.0.iter().map(|t| t.as_ref())
+    }
+}
+
+/// Wendet eine Umwandlung auf einen Texteintrag an und legt das Ergebnis als neuen
+/// Eintrag an; das Original bleibt erhalten.
+pub fn apply_to_entry(
+    history: &mut History,
+    index: usize,
+    transform: &dyn Transform,
+) -> Result<Entry> {
+    let entry = history
+        .entries
+        .get(index)
+        .ok_or_else(|| Error::NotFound(format!("Kein Eintrag mit ID {index}")))?;
+    let ClipboardItem::Text(text) = &entry.item else {
+        return Err(Error::Invalid(
+            "Nur Texteinträge können umgewandelt werden".into(),
+        ));
+    };
+
+    let result = transform.apply(text)?;
+    history.add_text(result);
+    history
+        .entries
+        .first()
+        .cloned()
+        .ok_or_else(|| Error::NotFound("Verlauf ist leer".into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn custom_transforms_override_builtins() {
+        let custom = CommandTransform {
+            name: "upper".into(),
+            description: "eigene Version".into(),
+            pipeline: vec!["tr a-z A-Z".into()],
+            timeout_ms: 1_000,
+        };
+        let transforms = Transforms::new(&[custom]);
+        assert_eq!(
+            transforms.get("upper").unwrap().description(),
+            "eigene Version"
+        );
+        assert_eq!(transforms.iter().filter(|t| t.name() == "upper").count(), 1);
+    }
+
+    #[test]
+    fn apply_keeps_original_entry() {
+        let transforms = Transforms::new(&[]);
+        let mut hist = History::new(5);
+        hist.add_text("  hallo  ".into());
+
+        let entry = apply_to_entry(&mut hist, 0, transforms.get("trim").unwrap()).unwrap();
+        assert_eq!(entry.content, "hallo");
+        assert_eq!(hist.entries.len(), 2);
+        assert_eq!(hist.entries[1].content, "  hallo  ");
+
+        hist.add_image("/tmp/hyprclip_transform.png".into(), 5);
+        assert!(apply_to_entry(&mut hist, 0, transforms.get("trim").unwrap()).is_err());
+    }
+}
@@ -1,36 +1,201 @@
-use crate::history::History;
+use super::{
+    editor::{self, EditOutcome, EditState},
+    images::{ImageLoader, ImageSize, TextureCache},
+    preview,
+    snippets::{self, FormOutcome, InputForm},
+    transform_picker::{self, PickerOutcome, PickerState},
+    window,
+};
+use crate::{
+    config::{Config, ConfigWatcher, SelectionConfig, WindowConfig},
+    error::Error,
+    fswatch::FileWatcher,
+    history::{ClipboardItem, Entry, History},
+    keymap::{Action, KeyChord, KeyOutcome, KeySequencer, Keymap},
+    snippets::SnippetStore,
+    transform::Transforms,
+    watcher::get_latest_entry,
+};
 use eframe::{egui, App, Frame};
-use egui::{Key, TextureHandle};
+use egui::{Key, Modifiers, TextureHandle};
+use egui_extras::{Column, TableBuilder};
 use std::{
-    collections::HashMap,
-    path::PathBuf,
-    sync::{Arc, Mutex},
-    time::SystemTime,
+    collections::HashSet,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver},
+        Arc, Mutex,
+    },
 };
+use tracing::{error, warn};
 
 pub struct HyprclipApp {
     shared_history: Arc<Mutex<History>>,
     selected_index: usize,
+    /// History-Indizes der Einträge, die zur aktuellen Suche passen
+    visible: Vec<usize>,
+    /// `visible` muss neu berechnet werden (Suche oder Verlauf geändert)
+    list_dirty: bool,
+    /// Zuletzt in den sichtbaren Bereich gescrollte Auswahl
+    scrolled_to: Option<usize>,
     storage_path: PathBuf,
-    image_cache: HashMap<PathBuf, TextureHandle>,
+    /// Meldet Änderungen der History-Datei (z. B. durch den Watcher-Prozess)
+    history_watcher: Option<FileWatcher>,
+    /// Meldet Änderungen der `config.toml`
+    config_watcher: ConfigWatcher,
+    loader: ImageLoader,
+    thumbnails: TextureCache,
+    full_images: TextureCache,
+    window_cfg: WindowConfig,
+    position: Option<(f32, f32)>,
+    placed: bool,
+    was_focused: bool,
+    keymap: Keymap,
+    sequencer: KeySequencer,
+    query: String,
+    paste_requested: Arc<AtomicBool>,
+    show_preview: bool,
+    preview: preview::PreviewState,
+    /// Markierte Einträge (über [`Entry::key`], damit Neuladen sie nicht verschiebt)
+    marked: HashSet<u64>,
+    selection_cfg: SelectionConfig,
+    /// Zielverzeichnis im Export-Feld
+    export_dir: String,
+    /// Rückmeldung der letzten Sammelaktion
+    status: Option<String>,
+    /// Gerade im Editor geöffneter Eintrag
+    editing: Option<EditState>,
+    transforms: Transforms,
+    /// Offene Umwandlungs-Auswahl
+    transforming: Option<PickerState>,
+    /// Laufende Umwandlung (Name und Ergebnis) – Befehle laufen nicht im UI-Thread
+    pending_transform: Option<(String, Receiver<crate::error::Result<String>>)>,
+    tab: Tab,
+    snippets: SnippetStore,
+    /// Snippet-Indizes passend zur Suche
+    snippet_visible: Vec<usize>,
+    snippet_index: usize,
+    /// Offenes Formular für `{input:…}`-Platzhalter
+    snippet_form: Option<InputForm>,
+}
+
+/// Reiter über der Liste
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tab {
+    History,
+    Snippets,
 }
 
+/// Maximale Zeichen der einzeiligen Listenansicht
+const LABEL_CHARS: usize = 60;
+/// Anzahl Vorschaubild-Texturen im Speicher
+const THUMBNAIL_CACHE_SIZE: usize = 64;
+/// Anzahl Originalbild-Texturen im Speicher (nur für die Vorschau)
+const FULL_IMAGE_CACHE_SIZE: usize = 4;
+
+/// Anzahl Zeilen, um die PageUp/PageDown springen
+const PAGE_SIZE: usize = 10;
+
+/// Feste Zeilenhöhen, damit die Liste nur sichtbare Zeilen zeichnen muss
+const ROW_HEIGHT: f32 = 24.0;
+const IMAGE_ROW_HEIGHT: f32 = 96.0;
+/// Breite der Vorschaubild-Spalte
+const THUMBNAIL_COLUMN_WIDTH: f32 = 160.0;
+
 impl HyprclipApp {
-    pub fn new(history: Arc<Mutex<History>>, storage_path: PathBuf) -> Self {
+    pub fn new(
+        ctx: &egui::Context,
+        history: Arc<Mutex<History>>,
+        cfg: &Config,
+        position: Option<(f32, f32)>,
+        paste_requested: Arc<AtomicBool>,
+    ) -> Self {
+        let (snippets, status) = match SnippetStore::load() {
+            Ok(store) => (store, None),
+            Err(e) => (SnippetStore::default(), Some(format!("❌ Snippets: {e}"))),
+        };
+
         Self {
             shared_history: history,
             selected_index: 0,
-            storage_path,
-            image_cache: HashMap::new(),
+            visible: Vec::new(),
+            list_dirty: true,
+            scrolled_to: None,
+            storage_path: cfg.storage_path.clone(),
+            history_watcher: Self::watch_file(ctx, &cfg.storage_path),
+            config_watcher: ConfigWatcher::new({
+                let ctx = ctx.clone();
+                move || ctx.request_repaint()
+            }),
+            loader: ImageLoader::new(ctx.clone()),
+            thumbnails: TextureCache::new(THUMBNAIL_CACHE_SIZE),
+            full_images: TextureCache::new(FULL_IMAGE_CACHE_SIZE),
+            window_cfg: cfg.window.clone(),
+            position,
+            placed: false,
+            was_focused: false,
+            keymap: cfg.keymap.clone(),
+            sequencer: KeySequencer::default(),
+            query: String::new(),
+            paste_requested,
+            show_preview: false,
+            preview: preview::PreviewState::default(),
+            marked: HashSet::new(),
+            selection_cfg: cfg.selection.clone(),
+            export_dir: cfg.selection.export_dir.display().to_string(),
+            status,
+            editing: None,
+            transforms: Transforms::new(&cfg.transforms),
+            transforming: None,
+            pending_transform: None,
+            tab: Tab::History,
+            snippets,
+            snippet_visible: Vec::new(),
+            snippet_index: 0,
+            snippet_form: None,
         }
     }
 
-    fn delete_selected(&mut self) {
+    fn search_id() -> egui::Id {
+        egui::Id::new("hyprclip_search")
+    }
+
+    // Platzierung erst nach dem ersten Frame – vorher ist das Fenster noch nicht gemappt
+    fn maybe_place_window(&mut self, ctx: &egui::Context) {
+        if !self.placed && ctx.cumulative_pass_nr() > 0 {
+            window::place_on_hyprland(&self.window_cfg, self.position);
+            self.placed = true;
+        }
+    }
+
+    // Schließt den Picker wie einen Launcher, sobald ein anderes Fenster den Fokus bekommt
+    fn close_on_focus_loss(&mut self, ctx: &egui::Context) {
+        if !self.window_cfg.close_on_focus_loss {
+            return;
+        }
+
+        match ctx.input(|i| i.viewport().focused) {
+            Some(true) => self.was_focused = true,
+            Some(false) if self.was_focused => {
+                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            }
+            _ => {}
+        }
+    }
+
+    fn delete_entry(&mut self, index: usize) {
         let mut history = self.shared_history.lock().unwrap();
-        if history.delete_entry(self.selected_index) {
+        let entry = history.entries.get(index).cloned();
+        if history.delete_entry(index) {
+            if let Some(entry) = entry {
+                entry.remove_files();
+                self.marked.remove(&entry.key());
+            }
             if let Err(e) = history.save(&self.storage_path) {
-                eprintln!("Fehler beim Speichern: {}", e);
+                error!("Verlauf konnte nicht gespeichert werden: {e}");
             }
+            self.list_dirty = true;
 
             if self.selected_index > 0 {
                 self.selected_index -= 1;
@@ -38,203 +203,877 @@ impl HyprclipApp {
         }
     }
 
-    // Letzter Änderungszeitpunkt der Datei, um unnötiges Neuladen zu vermeiden
-    fn maybe_reload_history(&mut self) {
-        // Nur laden, wenn sich Datei geändert hat
-        static mut LAST_MODIFIED: Option<SystemTime> = None;
-
-        if let Ok(metadata) = std::fs::metadata(&self.storage_path) {
-            if let Ok(modified) = metadata.modified() {
-                unsafe {
-                    if LAST_MODIFIED.map_or(true, |t| t != modified) {
-                        // History neu laden
-                        let new_hist = History::load(
-                            &self.storage_path,
-                            self.shared_history.lock().unwrap().limit,
-                        );
-
-                        // Hole alle neuen Pfade **vor** dem Move
-                        let entries: Vec<_> = new_hist
-                            .entries
-                            .iter()
-                            .map(|e| PathBuf::from(&e.content))
-                            .collect();
-
-                        // Schreibe new_hist in shared_history (Move)
-                        *self.shared_history.lock().unwrap() = new_hist;
-
-                        // Invalide cache für gelöschte/geänderte Pfade
-                        self.image_cache.retain(|k, _| entries.contains(k));
-
-                        LAST_MODIFIED = Some(modified);
-                    }
+    // Entfernt Einträge samt Bilddateien
+    fn delete_entries(&mut self, indices: &[usize]) {
+        let mut history = self.shared_history.lock().unwrap();
+        let removed = history.delete_entries(indices);
+        if removed.is_empty() {
+            return;
+        }
+        for entry in &removed {
+            entry.remove_files();
+            self.marked.remove(&entry.key());
+        }
+        if let Err(e) = history.save(&self.storage_path) {
+            error!("Verlauf konnte nicht gespeichert werden: {e}");
+        }
+        self.list_dirty = true;
+        self.selected_index = self.selected_index.saturating_sub(removed.len());
+    }
+
+    /// History-Indizes der markierten Einträge, neueste zuerst
+    fn marked_indices(&self) -> Vec<usize> {
+        let history = self.shared_history.lock().unwrap();
+        history
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| self.marked.contains(&e.key()))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    fn set_marked(&mut self, index: usize, marked: Option<bool>) {
+        let history = self.shared_history.lock().unwrap();
+        let Some(key) = history.entries.get(index).map(Entry::key) else {
+            return;
+        };
+        let mark = marked.unwrap_or(!self.marked.contains(&key));
+        if mark {
+            self.marked.insert(key);
+        } else {
+            self.marked.remove(&key);
+        }
+    }
+
+    // Markiert alle Zeilen zwischen zwei Positionen der (gefilterten) Liste
+    fn mark_range(&mut self, visible: &[usize], from: usize, to: usize) {
+        let (start, end) = (from.min(to), from.max(to));
+        for &index in visible.iter().take(end + 1).skip(start) {
+            self.set_marked(index, Some(true));
+        }
+    }
+
+    fn delete_marked(&mut self) {
+        let indices = self.marked_indices();
+        self.delete_entries(&indices);
+        self.status = Some(format!("🗑 {} Einträge gelöscht", indices.len()));
+        self.marked.clear();
+    }
+
+    // Sind alle markierten Einträge angepinnt, werden sie gelöst – sonst alle angepinnt
+    fn pin_marked(&mut self) {
+        let indices = self.marked_indices();
+        let mut history = self.shared_history.lock().unwrap();
+        let pin = !indices.iter().all(|&i| history.entries[i].pinned);
+        history.set_pinned(&indices, pin);
+        if let Err(e) = history.save(&self.storage_path) {
+            error!("Verlauf konnte nicht gespeichert werden: {e}");
+        }
+        self.list_dirty = true;
+    }
+
+    fn merge_marked(&mut self) {
+        let indices = self.marked_indices();
+        let mut history = self.shared_history.lock().unwrap();
+        let Some(entry) = history.merge_texts(&indices, &self.selection_cfg.merge_separator) else {
+            self.status = Some("Keine Texte markiert.".to_string());
+            return;
+        };
+
+        let _ = crate::clipboard::set_clipboard_item_no_ignore(&entry.item);
+        if let Err(e) = history.save(&self.storage_path) {
+            error!("Verlauf konnte nicht gespeichert werden (Zusammenführen): {e}");
+        }
+        self.status = Some(format!("🔗 {} Einträge zusammengeführt", indices.len()));
+        self.marked.clear();
+        self.selected_index = 0;
+        self.list_dirty = true;
+    }
+
+    fn export_marked(&mut self) {
+        let indices = self.marked_indices();
+        let dir = PathBuf::from(&self.export_dir);
+        let result = self
+            .shared_history
+            .lock()
+            .unwrap()
+            .export_entries(&indices, &dir);
+        self.status = Some(match result {
+            Ok(n) => format!("💾 {n} Einträge nach {} exportiert", dir.display()),
+            Err(e) => format!("❌ Export fehlgeschlagen: {e}"),
+        });
+    }
+
+    fn start_editing(&mut self, index: usize) {
+        let history = self.shared_history.lock().unwrap();
+        let Some(entry) = history.entries.get(index) else {
+            return;
+        };
+        match &entry.item {
+            ClipboardItem::Text(text) => {
+                self.editing = Some(EditState::new(entry.key(), text.clone()));
+            }
+            ClipboardItem::Image(_) => {
+                self.status = Some("Bilder lassen sich nicht bearbeiten.".to_string());
+            }
+        }
+    }
+
+    // Ergebnis des Editors übernehmen; der Eintrag wird über seinen Schlüssel gesucht,
+    // weil der Watcher den Verlauf zwischenzeitlich verändert haben kann
+    fn finish_editing(&mut self, outcome: EditOutcome) {
+        let Some(state) = self.editing.take() else {
+            return;
+        };
+
+        let mut history = self.shared_history.lock().unwrap();
+        let entry = match outcome {
+            EditOutcome::Cancel => return,
+            EditOutcome::Replace => history
+                .entries
+                .iter()
+                .position(|e| e.key() == state.key)
+                .and_then(|index| history.replace_text(index, state.text)),
+            EditOutcome::SaveNew => {
+                history.add_text(state.text);
+                self.selected_index = 0;
+                history.entries.first().cloned()
+            }
+        };
+
+        let Some(entry) = entry else {
+            self.status = Some("❌ Eintrag nicht mehr vorhanden.".to_string());
+            return;
+        };
+        let _ = crate::clipboard::set_clipboard_item_no_ignore(&entry.item);
+        if let Err(e) = history.save(&self.storage_path) {
+            error!("Verlauf konnte nicht gespeichert werden (Bearbeiten): {e}");
+        }
+        self.list_dirty = true;
+    }
+
+    fn finish_transform(&mut self, ctx: &egui::Context, outcome: PickerOutcome) {
+        let (Some(state), PickerOutcome::Apply(name)) = (self.transforming.take(), outcome) else {
+            return;
+        };
+        let Some(transform) = self.transforms.shared(&name) else {
+            return;
+        };
+
+        // Nur zum Kopieren des Texts sperren; die Umwandlung selbst kann dauern
+        let text = {
+            let history = self.shared_history.lock().unwrap();
+            match history.entries.iter().find(|e| e.key() == state.key) {
+                Some(Entry {
+                    item: ClipboardItem::Text(text),
+                    ..
+                }) => text.clone(),
+                Some(_) => {
+                    self.status = Some("❌ Nur Texteinträge können umgewandelt werden".into());
+                    return;
+                }
+                None => {
+                    self.status = Some("❌ Eintrag nicht mehr vorhanden".into());
+                    return;
                 }
             }
+        };
+
+        let (tx, rx) = mpsc::channel();
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            let _ = tx.send(transform.apply(&text));
+            ctx.request_repaint();
+        });
+        self.status = Some(format!("🔀 {name} läuft …"));
+        self.pending_transform = Some((name, rx));
+    }
+
+    /// Übernimmt das Ergebnis einer fertigen Umwandlung als neuen Eintrag
+    fn poll_transform(&mut self) {
+        let Some((name, rx)) = &self.pending_transform else {
+            return;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                Err(Error::Command(format!("{name}: abgebrochen")))
+            }
+        };
+        let name = name.clone();
+        self.pending_transform = None;
+
+        match result {
+            Ok(text) => {
+                let mut history = self.shared_history.lock().unwrap();
+                history.add_text(text);
+                if let Some(entry) = history.entries.first() {
+                    let _ = crate::clipboard::set_clipboard_item_no_ignore(&entry.item);
+                }
+                if let Err(e) = history.save(&self.storage_path) {
+                    error!("Verlauf konnte nicht gespeichert werden (Umwandlung): {e}");
+                }
+                self.status = Some(format!("🔀 {name} angewendet"));
+                self.selected_index = 0;
+                self.list_dirty = true;
+            }
+            Err(e) => self.status = Some(format!("❌ {e}")),
         }
     }
 
-    fn handle_key_inputs(&mut self, ctx: &egui::Context, entries_len: usize) {
-        if ctx.input(|i| i.key_pressed(Key::ArrowDown)) && self.selected_index + 1 < entries_len {
-            self.selected_index += 1;
+    // Leiste mit Sammelaktionen, solange etwas markiert ist
+    fn show_selection_bar(&mut self, ui: &mut egui::Ui) {
+        if self.marked.is_empty() {
+            if let Some(status) = &self.status {
+                ui.label(status);
+            }
+            return;
         }
-        if ctx.input(|i| i.key_pressed(Key::ArrowUp)) && self.selected_index > 0 {
-            self.selected_index -= 1;
+
+        ui.horizontal_wrapped(|ui| {
+            ui.label(format!("{} markiert", self.marked.len()));
+            if ui.button("🔗 Zusammenführen").clicked() {
+                self.merge_marked();
+            }
+            if ui.button("📌 Anpinnen").clicked() {
+                self.pin_marked();
+            }
+            if ui.button("🗑 Löschen").clicked() {
+                self.delete_marked();
+            }
+            ui.add(egui::TextEdit::singleline(&mut self.export_dir).desired_width(160.0));
+            if ui.button("💾 Exportieren").clicked() {
+                self.export_marked();
+            }
+            if ui.button("✖ Aufheben").clicked() {
+                self.marked.clear();
+            }
+        });
+        if let Some(status) = &self.status {
+            ui.label(status);
         }
-        if ctx.input(|i| i.key_pressed(Key::Delete)) {
-            self.delete_selected();
+    }
+
+    fn toggle_pin(&mut self, index: usize) {
+        let mut history = self.shared_history.lock().unwrap();
+        if history.toggle_pin(index).is_some() {
+            if let Err(e) = history.save(&self.storage_path) {
+                error!("Verlauf konnte nicht gespeichert werden: {e}");
+            }
+            self.list_dirty = true;
         }
-        if ctx.input(|i| i.key_pressed(Key::Escape)) {
-            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+    }
+
+    // Weckt die GUI nur bei echten Änderungen auf, statt die Datei jeden Frame zu prüfen
+    fn watch_file(ctx: &egui::Context, path: &Path) -> Option<FileWatcher> {
+        let ctx = ctx.clone();
+        match FileWatcher::new(path, move || ctx.request_repaint()) {
+            Ok(watcher) => Some(watcher),
+            Err(e) => {
+                warn!(path = %path.display(), "Datei wird nicht automatisch neu geladen: {e}");
+                None
+            }
         }
-        if ctx.input(|i| i.key_pressed(Key::Enter)) && self.selected_index <= entries_len {
-            self.select_entry(self.selected_index);
+    }
+
+    /// Übernimmt eine geänderte `config.toml`; bei Fehlern bleibt die bisherige aktiv
+    fn maybe_reload_config(&mut self, ctx: &egui::Context) {
+        if !self.config_watcher.poll() {
+            // Erst nach der Wartezeit laden, auch wenn bis dahin kein Ereignis mehr kommt
+            if let Some(wait) = self.config_watcher.pending() {
+                ctx.request_repaint_after(wait);
+            }
+            return;
         }
+
+        let cfg = match Config::load() {
+            Ok(cfg) => cfg,
+            Err(e) => {
+                error!("{e} – bisherige Konfiguration bleibt aktiv");
+                self.status = Some(format!("❌ {e}"));
+                return;
+            }
+        };
+        if cfg.storage_path != self.storage_path {
+            self.history_watcher = Self::watch_file(ctx, &cfg.storage_path);
+            self.storage_path = cfg.storage_path.clone();
+            *self.shared_history.lock().unwrap() =
+                History::load(&cfg.storage_path, cfg.history_limit);
+            self.list_dirty = true;
+        }
+        self.shared_history.lock().unwrap().limit = cfg.history_limit;
+        self.window_cfg = cfg.window;
+        self.keymap = cfg.keymap;
+        self.sequencer = KeySequencer::default();
+        self.selection_cfg = cfg.selection;
+        self.transforms = Transforms::new(&cfg.transforms);
+        self.status = Some("🔄 Konfiguration neu geladen".to_string());
     }
 
-    fn select_entry(&mut self, index: usize) {
-        let mut history = self.shared_history.lock().unwrap();
+    /// Lädt den Verlauf neu, wenn der Watcher eine Änderung der Datei gemeldet hat
+    fn maybe_reload_history(&mut self) {
+        if !self
+            .history_watcher
+            .as_ref()
+            .is_some_and(FileWatcher::take_changed)
+        {
+            return;
+        }
+
+        // Nicht lesbar (z. B. von Hand kaputt editiert) → bisherigen Verlauf behalten,
+        // sonst würde das nächste Speichern ihn leeren
+        let limit = self.shared_history.lock().unwrap().limit;
+        let Some(new_hist) = History::try_load(&self.storage_path, limit) else {
+            return;
+        };
+
+        // Hole alle neuen Pfade **vor** dem Move
+        let entries: Vec<_> = new_hist
+            .entries
+            .iter()
+            .map(|e| PathBuf::from(&e.content))
+            .collect();
 
-        if index >= history.entries.len() {
+        *self.shared_history.lock().unwrap() = new_hist;
+
+        // Invalide cache für gelöschte/geänderte Pfade
+        self.thumbnails.retain(|k| entries.iter().any(|e| e == k));
+        self.full_images.retain(|k| entries.iter().any(|e| e == k));
+        self.list_dirty = true;
+    }
+
+    // Tastendrücke über die Keymap auflösen; `visible` enthält die History-Indizes der Liste
+    fn handle_key_inputs(&mut self, ctx: &egui::Context, visible: &[usize]) {
+        let search_focused = ctx.memory(|m| m.has_focus(Self::search_id()));
+        let presses: Vec<(Key, Modifiers)> = ctx.input(|i| {
+            i.events
+                .iter()
+                .filter_map(|e| match e {
+                    egui::Event::Key {
+                        key,
+                        pressed: true,
+                        modifiers,
+                        ..
+                    } => Some((*key, *modifiers)),
+                    _ => None,
+                })
+                .collect()
+        });
+
+        for (key, modifiers) in presses {
+            if search_focused {
+                if KeyChord::is_handled_by_text_field(key, modifiers) {
+                    continue;
+                }
+                // Escape verlässt zuerst nur das Suchfeld
+                if key == Key::Escape {
+                    ctx.memory_mut(|m| m.surrender_focus(Self::search_id()));
+                    self.sequencer.reset();
+                    continue;
+                }
+            }
+
+            // Alt+1..9 wählt direkt den n-ten Eintrag
+            if let Some(n) = nth_entry_shortcut(key, modifiers) {
+                if self.tab == Tab::Snippets {
+                    if let Some(&index) = self.snippet_visible.get(n) {
+                        self.start_snippet(ctx, index, false);
+                    }
+                } else if let Some(&index) = visible.get(n) {
+                    self.select_entry(index);
+                }
+                self.sequencer.reset();
+                continue;
+            }
+
+            if let KeyOutcome::Action(action) = self.sequencer.feed(&self.keymap, key, modifiers) {
+                self.run_action(ctx, action, visible);
+            }
+        }
+    }
+
+    fn run_action(&mut self, ctx: &egui::Context, action: Action, visible: &[usize]) {
+        if self.tab == Tab::Snippets && self.run_snippet_action(ctx, action) {
             return;
         }
 
-        let entry = history.entries.remove(index);
+        let last = visible.len().saturating_sub(1);
+        let current = visible.get(self.selected_index).copied();
 
-        // Entferne andere Duplikate
-        history.entries.retain(|e| e.content != entry.content);
+        match action {
+            Action::Next => self.selected_index = (self.selected_index + 1).min(last),
+            Action::Prev => self.selected_index = self.selected_index.saturating_sub(1),
+            Action::PageDown => self.selected_index = (self.selected_index + PAGE_SIZE).min(last),
+            Action::PageUp => self.selected_index = self.selected_index.saturating_sub(PAGE_SIZE),
+            Action::Top => self.selected_index = 0,
+            Action::Bottom => self.selected_index = last,
+            Action::Select => {
+                if let Some(index) = current {
+                    self.select_entry(index);
+                }
+            }
+            Action::SelectAndPaste => {
+                if let Some(index) = current {
+                    self.select_and_paste(ctx, index);
+                }
+            }
+            Action::Delete if !self.marked.is_empty() => self.delete_marked(),
+            Action::Delete => {
+                if let Some(index) = current {
+                    self.delete_entry(index);
+                }
+            }
+            Action::Pin if !self.marked.is_empty() => self.pin_marked(),
+            Action::Pin => {
+                if let Some(index) = current {
+                    self.toggle_pin(index);
+                }
+            }
+            Action::ToggleMark => {
+                if let Some(index) = current {
+                    self.set_marked(index, None);
+                }
+            }
+            Action::MarkNext | Action::MarkPrev => {
+                let from = self.selected_index;
+                self.selected_index = if action == Action::MarkNext {
+                    (from + 1).min(last)
+                } else {
+                    from.saturating_sub(1)
+                };
+                self.mark_range(visible, from, self.selected_index);
+            }
+            Action::Merge => self.merge_marked(),
+            Action::Edit => {
+                if let Some(index) = current {
+                    self.start_editing(index);
+                }
+            }
+            Action::Transform => {
+                if let Some(index) = current {
+                    let history = self.shared_history.lock().unwrap();
+                    if let Some(entry) = history.entries.get(index) {
+                        self.transforming = Some(PickerState::new(entry.key()));
+                    }
+                }
+            }
+            Action::SwitchTab => self.switch_tab(),
+            Action::Preview => self.show_preview = !self.show_preview,
+            Action::SearchFocus => {
+                ctx.memory_mut(|m| m.request_focus(Self::search_id()));
+                // Das auslösende Zeichen (z. B. "/") nicht ins Suchfeld übernehmen
+                ctx.input_mut(|i| i.events.retain(|e| !matches!(e, egui::Event::Text(_))));
+            }
+            // Escape & Co. heben zuerst die Markierung auf
+            Action::Quit if !self.marked.is_empty() => self.marked.clear(),
+            Action::Quit => ctx.send_viewport_cmd(egui::ViewportCommand::Close),
+        }
+    }
 
-        // Setze an den Anfang
-        history.entries.insert(0, entry.clone());
+    /// Navigation und Auswahl im Snippet-Reiter; `false` = wie im Verlauf behandeln
+    fn run_snippet_action(&mut self, ctx: &egui::Context, action: Action) -> bool {
+        let last = self.snippet_visible.len().saturating_sub(1);
+        let current = self.snippet_visible.get(self.snippet_index).copied();
 
-        // Bild-Einträge: Hash ggf. nachtragen, Clip setzen ohne erneute Erkennung
-        if let crate::history::ClipboardItem::Image(ref path) = entry.item {
-            if history.entries[0].hash.is_none() {
-                if let Ok(data) = std::fs::read(path) {
-                    history.entries[0].hash = Some(crate::util::hash_data(&data));
+        match action {
+            Action::Next => self.snippet_index = (self.snippet_index + 1).min(last),
+            Action::Prev => self.snippet_index = self.snippet_index.saturating_sub(1),
+            Action::PageDown => self.snippet_index = (self.snippet_index + PAGE_SIZE).min(last),
+            Action::PageUp => self.snippet_index = self.snippet_index.saturating_sub(PAGE_SIZE),
+            Action::Top => self.snippet_index = 0,
+            Action::Bottom => self.snippet_index = last,
+            Action::Select | Action::SelectAndPaste => {
+                if let Some(index) = current {
+                    self.start_snippet(ctx, index, action == Action::SelectAndPaste);
                 }
             }
+            Action::SwitchTab | Action::SearchFocus | Action::Quit => return false,
+            // Aktionen für Verlaufseinträge gelten hier nicht
+            _ => {}
+        }
+        true
+    }
+
+    fn switch_tab(&mut self) {
+        self.tab = match self.tab {
+            Tab::History => Tab::Snippets,
+            Tab::Snippets => Tab::History,
+        };
+    }
+
+    /// Fragt erst die Eingaben ab, falls das Snippet welche braucht
+    fn start_snippet(&mut self, ctx: &egui::Context, index: usize, paste: bool) {
+        let Some(snippet) = self.snippets.snippets.get(index) else {
+            return;
+        };
+        let form = InputForm::new(index, snippet, paste);
+        if form.values.is_empty() {
+            self.insert_snippet(ctx, form);
+        } else {
+            self.snippet_form = Some(form);
         }
+    }
 
+    /// Füllt die Platzhalter aus und legt das Ergebnis als neuen Eintrag ins Clipboard
+    fn insert_snippet(&mut self, ctx: &egui::Context, form: InputForm) {
+        let Some(snippet) = self.snippets.snippets.get(form.index) else {
+            return;
+        };
+        let text = match snippet.expand(&form.inputs(), || get_latest_entry().ok()) {
+            Ok(text) => text,
+            Err(e) => {
+                self.status = Some(format!("❌ {}: {e}", snippet.name));
+                return;
+            }
+        };
+
+        let mut history = self.shared_history.lock().unwrap();
+        history.add_text(text);
+        if let Some(entry) = history.entries.first() {
+            let _ = crate::clipboard::set_clipboard_item_no_ignore(&entry.item);
+        }
+        if let Err(e) = history.save(&self.storage_path) {
+            error!("Verlauf konnte nicht gespeichert werden (Snippet): {e}");
+        }
+        self.status = Some(format!("🧩 {} eingefügt", snippet.name));
+        self.selected_index = 0;
+        self.list_dirty = true;
+
+        if form.paste {
+            self.paste_requested.store(true, Ordering::SeqCst);
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+        }
+    }
+
+    fn select_entry(&mut self, index: usize) {
+        let mut history = self.shared_history.lock().unwrap();
+
+        let Some(entry) = history.promote(index) else {
+            return;
+        };
+
+        // Clip setzen ohne erneute Erkennung
         let _ = crate::clipboard::set_clipboard_item_no_ignore(&entry.item);
 
         self.selected_index = 0;
+        self.list_dirty = true;
 
         if let Err(e) = history.save(&self.storage_path) {
-            eprintln!("Fehler beim Speichern nach select_entry: {}", e);
+            error!("Verlauf konnte nicht gespeichert werden (Auswahl): {e}");
         }
     }
 
-    fn fallback_texture(ctx: &egui::Context, path: &PathBuf) -> egui::TextureHandle {
-        // Erzeuge ein 1x1 transparentes Bild als Platzhalter
-        let fallback_image = egui::ColorImage::from_rgba_unmultiplied([1, 1], &[0, 0, 0, 0]);
-        ctx.load_texture(
-            format!("fallback:{}", path.to_string_lossy()),
-            fallback_image,
-            egui::TextureOptions::default(),
-        )
+    // Auswahl setzen, Picker schließen – das Einfügen übernimmt der Aufrufer nach dem Fensterende
+    fn select_and_paste(&mut self, ctx: &egui::Context, index: usize) {
+        self.select_entry(index);
+        self.paste_requested.store(true, Ordering::SeqCst);
+        ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+    }
+
+    /// Filtert den Verlauf nur neu, wenn sich Suche oder Einträge geändert haben
+    fn refresh_visible(&mut self) {
+        if self.list_dirty {
+            let history = self.shared_history.lock().unwrap();
+            self.visible = history
+                .entries
+                .iter()
+                .enumerate()
+                .filter(|(_, e)| e.matches(&self.query))
+                .map(|(i, _)| i)
+                .collect();
+            self.list_dirty = false;
+        }
+        self.selected_index = self
+            .selected_index
+            .min(self.visible.len().saturating_sub(1));
+    }
+
+    fn refresh_snippets(&mut self) {
+        self.snippet_visible = self.snippets.filter(&self.query);
+        self.snippet_index = self
+            .snippet_index
+            .min(self.snippet_visible.len().saturating_sub(1));
+    }
+
+    fn is_image_file(path: &Path) -> bool {
+        path.is_file()
+            && path
+                .extension()
+                .is_some_and(|ext| ext == "png" || ext == "jpg")
+    }
+
+    /// Liefert die Textur aus dem Cache oder stößt das Laden im Hintergrund an
+    fn texture_for(&mut self, path: &Path, size: ImageSize) -> Option<TextureHandle> {
+        let cache = match size {
+            ImageSize::Thumbnail => &mut self.thumbnails,
+            ImageSize::Full => &mut self.full_images,
+        };
+        let texture = cache.get(path);
+        if texture.is_none() {
+            self.loader.request(path, size);
+        }
+        texture
+    }
+
+    /// Zeichnet nur die sichtbaren Zeilen; der Verlauf bleibt dabei gesperrt statt kopiert
+    fn show_list(&mut self, ui: &mut egui::Ui) {
+        let history = Arc::clone(&self.shared_history);
+        let history = history.lock().unwrap();
+        let visible = std::mem::take(&mut self.visible);
+        let marked = std::mem::take(&mut self.marked);
+        let mut clicked = None;
+
+        let mut table = TableBuilder::new(ui)
+            .id_salt("history_table")
+            .striped(true)
+            .auto_shrink([false, false])
+            .column(Column::remainder().clip(true))
+            .column(Column::exact(THUMBNAIL_COLUMN_WIDTH));
+
+        // Nur bei geänderter Auswahl scrollen, sonst könnte man mit der Maus nicht mehr blättern
+        if self.scrolled_to != Some(self.selected_index) {
+            table = table.scroll_to_row(self.selected_index, Some(egui::Align::Center));
+            self.scrolled_to = Some(self.selected_index);
+        }
+
+        let heights = visible
+            .iter()
+            .map(|&i| match history.entries[i].item {
+                ClipboardItem::Image(_) => IMAGE_ROW_HEIGHT,
+                ClipboardItem::Text(_) => ROW_HEIGHT,
+            })
+            .collect::<Vec<_>>();
+
+        table.body(|body| {
+            body.heterogeneous_rows(heights.into_iter(), |mut row| {
+                let pos = row.index();
+                let i = visible[pos];
+                let entry = &history.entries[i];
+                let sel = pos == self.selected_index;
+
+                // 👉 Spalte 1: Eintragsname
+                row.col(|ui| {
+                    let response = ui.selectable_label(
+                        sel,
+                        format!(
+                            "{}{}{} {}",
+                            if marked.contains(&entry.key()) {
+                                "☑ "
+                            } else {
+                                ""
+                            },
+                            if entry.pinned { "📌 " } else { "" },
+                            entry.kind.badge(),
+                            entry.summary(LABEL_CHARS)
+                        ),
+                    );
+                    if response.clicked() {
+                        clicked = Some((pos, ui.input(|i| i.modifiers)));
+                    }
+                });
+
+                // ✅ Spalte 2: Thumbnail (falls Bild)
+                row.col(|ui| match &entry.item {
+                    ClipboardItem::Image(path) if Self::is_image_file(path) => {
+                        match self.texture_for(path, ImageSize::Thumbnail) {
+                            Some(texture) => {
+                                ui.add(
+                                    egui::Image::new(&texture)
+                                        .max_height(IMAGE_ROW_HEIGHT - 4.0)
+                                        .max_width(THUMBNAIL_COLUMN_WIDTH),
+                                );
+                            }
+                            // Platzhalter, bis der Worker das Vorschaubild liefert
+                            None => {
+                                ui.spinner();
+                            }
+                        }
+                    }
+                    // 👉 Kein Bild: Platzhalter
+                    _ => {
+                        ui.label("-");
+                    }
+                });
+            });
+        });
+
+        drop(history);
+        self.marked = marked;
+        // Strg+Klick markiert einzeln, Shift+Klick einen Bereich ab der Auswahl
+        match clicked {
+            Some((pos, modifiers)) if modifiers.command => {
+                self.set_marked(visible[pos], None);
+                self.selected_index = pos;
+            }
+            Some((pos, modifiers)) if modifiers.shift => {
+                self.mark_range(&visible, self.selected_index, pos);
+                self.selected_index = pos;
+            }
+            Some((pos, _)) => self.select_entry(visible[pos]),
+            None => {}
+        }
+        self.visible = visible;
+    }
+
+    fn show_preview_panel(&mut self, ctx: &egui::Context, entry: &Entry) {
+        let image = match &entry.item {
+            ClipboardItem::Image(path) if Self::is_image_file(path) => {
+                Some(self.texture_for(path, ImageSize::Full))
+            }
+            _ => None,
+        };
+
+        egui::SidePanel::right("preview_panel")
+            .resizable(true)
+            .default_width(ctx.screen_rect().width() * 0.5)
+            .show(ctx, |ui| match image {
+                // Bild wird noch im Hintergrund geladen
+                Some(None) => {
+                    ui.centered_and_justified(|ui| ui.spinner());
+                }
+                Some(texture) => preview::show(ui, &mut self.preview, entry, texture.as_ref()),
+                None => preview::show(ui, &mut self.preview, entry, None),
+            });
     }
 }
 
 impl App for HyprclipApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut Frame) {
+        self.maybe_place_window(ctx);
+        self.close_on_focus_loss(ctx);
         self.maybe_reload_history();
-        let entries = { self.shared_history.lock().unwrap().entries.clone() };
+        self.maybe_reload_config(ctx);
+        self.poll_transform();
+        self.loader
+            .upload_finished(ctx, &mut self.thumbnails, &mut self.full_images);
+        self.refresh_visible();
+        self.refresh_snippets();
+
+        // ✏️ Im Editor gelten die Tasten des Textfelds statt der Keymap
+        if let Some(state) = &mut self.editing {
+            let outcome = egui::CentralPanel::default()
+                .show(ctx, |ui| editor::show(ui, state))
+                .inner;
+            if let Some(outcome) = outcome {
+                self.finish_editing(outcome);
+            }
+            return;
+        }
+        if let Some(state) = &mut self.transforming {
+            let outcome = egui::CentralPanel::default()
+                .show(ctx, |ui| {
+                    transform_picker::show(ui, state, &self.transforms)
+                })
+                .inner;
+            if let Some(outcome) = outcome {
+                self.finish_transform(ctx, outcome);
+            }
+            return;
+        }
+
+        if let Some(form) = &mut self.snippet_form {
+            let snippet = &self.snippets.snippets[form.index];
+            let outcome = egui::CentralPanel::default()
+                .show(ctx, |ui| snippets::show_form(ui, form, snippet))
+                .inner;
+            match outcome {
+                Some(FormOutcome::Insert) => {
+                    if let Some(form) = self.snippet_form.take() {
+                        self.insert_snippet(ctx, form);
+                    }
+                }
+                Some(FormOutcome::Cancel) => self.snippet_form = None,
+                None => {}
+            }
+            return;
+        }
+
+        // 🔑 Eingaben über die Keymap verarbeiten
+        let visible = std::mem::take(&mut self.visible);
+        self.handle_key_inputs(ctx, &visible);
+        self.visible = visible;
+        self.refresh_visible();
 
-        // 🔑 Eingaben verarbeiten (Up, Down, Delete, Escape)
-        self.handle_key_inputs(ctx, entries.len());
+        // 🔍 Vorschau des ausgewählten Eintrags (Panel muss vor dem CentralPanel kommen)
+        if self.show_preview {
+            let selected = self.visible.get(self.selected_index).and_then(|&index| {
+                self.shared_history
+                    .lock()
+                    .unwrap()
+                    .entries
+                    .get(index)
+                    .cloned()
+            });
+            if let Some(entry) = selected {
+                self.show_preview_panel(ctx, &entry);
+            }
+        }
 
         egui::CentralPanel::default().show(ctx, |ui| {
-            ui.heading("📋 Clipboard-Verlauf");
+            ui.horizontal(|ui| {
+                ui.selectable_value(&mut self.tab, Tab::History, "📋 Verlauf");
+                ui.selectable_value(&mut self.tab, Tab::Snippets, "🧩 Snippets");
+            });
+            let search = ui.add(
+                egui::TextEdit::singleline(&mut self.query)
+                    .id(Self::search_id())
+                    .hint_text("🔍 Suchen… (/), Filter z. B. kind:url")
+                    .desired_width(f32::INFINITY),
+            );
+            if search.changed() {
+                self.selected_index = 0;
+                self.snippet_index = 0;
+                self.list_dirty = true;
+                self.refresh_visible();
+                self.refresh_snippets();
+            }
+            self.show_selection_bar(ui);
             ui.separator();
 
-            if entries.is_empty() {
+            if self.tab == Tab::Snippets {
+                if self.snippet_visible.is_empty() {
+                    ui.label(format!(
+                        "Keine Snippets. Anlegen in {}",
+                        SnippetStore::path().display()
+                    ));
+                } else if let Some(pos) = snippets::show_list(
+                    ui,
+                    &self.snippets,
+                    &self.snippet_visible,
+                    self.snippet_index,
+                ) {
+                    self.snippet_index = pos;
+                    if let Some(&index) = self.snippet_visible.get(pos) {
+                        self.start_snippet(ctx, index, false);
+                    }
+                }
+            } else if self.visible.is_empty() {
                 ui.label("Keine Einträge.");
             } else {
-                egui::ScrollArea::vertical().show(ui, |ui| {
-                    egui::Grid::new("history_grid")
-                        .striped(true)
-                        .spacing([20.0, 8.0])
-                        .show(ui, |ui| {
-                            for (i, entry) in entries.iter().enumerate() {
-                                let sel = i == self.selected_index;
-                                let path = PathBuf::from(&entry.content);
-
-                                // 👉 Spalte 1: Eintragsname
-                                let response = ui.selectable_label(
-                                    sel,
-                                    path.file_name()
-                                        .unwrap_or_default()
-                                        .to_string_lossy()
-                                );
-
-                                // ✅ Spalte 2: Thumbnail (falls Bild)
-                                if path.exists()
-                                    && path.is_file()
-                                    && (entry.content.ends_with(".png") || entry.content.ends_with(".jpg"))
-                                {
-                                    let texture: &TextureHandle = {
-                                        let entry = self.image_cache.entry(path.clone());
-                                        entry.or_insert_with(|| {
-                                            println!("🔄 Lade Bild: {:?}", path);
-
-                                            match std::fs::read(&path) {
-                                                Ok(image_data) if !image_data.is_empty() => {
-                                                    match image::load_from_memory(&image_data) {
-                                                        Ok(img) => {
-                                                            let img = img.to_rgba8();
-                                                            let size = [img.width() as _, img.height() as _];
-                                                            println!("✅ Bild erfolgreich geladen: {:?}, Größe: {:?}", path, size);
-
-                                                            let color_image = egui::ColorImage::from_rgba_unmultiplied(
-                                                                size,
-                                                                img.as_flat_samples().as_slice(),
-                                                            );
-                                                            ctx.load_texture(
-                                                                path.to_string_lossy(),
-                                                                color_image,
-                                                                egui::TextureOptions::default(),
-                                                            )
-                                                        }
-                                                        Err(e) => {
-                                                            eprintln!("❌ Fehler beim Dekodieren: {:?}: {}", path, e);
-                                                            Self::fallback_texture(ctx, &path)
-                                                        }
-                                                    }
-                                                }
-                                                Ok(_) => {
-                                                    eprintln!("❌ Bilddatei ist leer: {:?}", path);
-                                                    Self::fallback_texture(ctx, &path)
-                                                }
-                                                Err(e) => {
-                                                    eprintln!("❌ Fehler beim Lesen: {:?}: {}", path, e);
-                                                    Self::fallback_texture(ctx, &path)
-                                                }
-                                            }
-                                        })
-                                    };
-
-                                    ui.add(egui::Image::new(texture).max_height(150.0).max_width(400.0));
-                                } else {
-                                    // 👉 Kein Bild: Platzhalter
-                                    ui.label("-");
-                                }
-
-                                // ✅ Zeilenabschluss
-                                ui.end_row();
-
-                                // ✅ scroll_to_me & clicked Handling
-                                if sel {
-                                    response.scroll_to_me(Some(egui::Align::Center));
-                                }
-                                if response.clicked() {
-                                    self.select_entry(i);
-                                }
-                            }
-                        });
-                });
+                self.show_list(ui);
             }
         });
+    }
+}
 
-        ctx.request_repaint_after(std::time::Duration::from_millis(100));
+/// Alt+1..9 → Index 0..8
+fn nth_entry_shortcut(key: Key, modifiers: Modifiers) -> Option<usize> {
+    if !modifiers.alt || modifiers.ctrl {
+        return None;
     }
+    let n = match key {
+        Key::Num1 => 0,
+        Key::Num2 => 1,
+        Key::Num3 => 2,
+        Key::Num4 => 3,
+        Key::Num5 => 4,
+        Key::Num6 => 5,
+        Key::Num7 => 6,
+        Key::Num8 => 7,
+        Key::Num9 => 8,
+        _ => return None,
+    };
+    Some(n)
 }
@@ -0,0 +1,78 @@
+use eframe::egui;
+use egui::{Key, Modifiers};
+
+/// Was nach dem Bearbeiten mit dem Text passieren soll
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditOutcome {
+    /// Eintrag an seiner Position ersetzen
+    Replace,
+    /// Als neuen Eintrag speichern
+    SaveNew,
+    Cancel,
+}
+
+/// Bearbeitungszustand eines Texteintrags
+pub struct EditState {
+    /// [`crate::history::Entry::key`] des bearbeiteten Eintrags
+    pub key: u64,
+    pub text: String,
+    /// Fokus nur beim ersten Frame ins Textfeld setzen
+    focused: bool,
+}
+
+impl EditState {
+    pub fn new(key: u64, text: String) -> Self {
+        Self {
+            key,
+            text,
+            focused: false,
+        }
+    }
+}
+
+/// Mehrzeiliger Editor; `Ctrl+Enter` speichert neu, `Ctrl+Shift+Enter` ersetzt, `Escape` bricht ab
+pub fn show(ui: &mut egui::Ui, state: &mut EditState) -> Option<EditOutcome> {
+    let mut outcome = ui.input_mut(|i| {
+        if i.consume_key(Modifiers::CTRL | Modifiers::SHIFT, Key::Enter) {
+            Some(EditOutcome::Replace)
+        } else if i.consume_key(Modifiers::CTRL, Key::Enter) {
+            Some(EditOutcome::SaveNew)
+        } else if i.consume_key(Modifiers::NONE, Key::Escape) {
+            Some(EditOutcome::Cancel)
+        } else {
+            None
+        }
+    });
+
+    ui.heading("✏️ Eintrag bearbeiten");
+    ui.horizontal(|ui| {
+        if ui.button("💾 Als neuer Eintrag (Ctrl+Enter)").clicked() {
+            outcome = Some(EditOutcome::SaveNew);
+        }
+        if ui.button("♻ Ersetzen (Ctrl+Shift+Enter)").clicked() {
+            outcome = Some(EditOutcome::Replace);
+        }
+        if ui.button("Abbrechen (Esc)").clicked() {
+            outcome = Some(EditOutcome::Cancel);
+        }
+    });
+    ui.separator();
+
+    egui::ScrollArea::vertical()
+        .id_salt("editor")
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            let response = ui.add(
+                egui::TextEdit::multiline(&mut state.text)
+                    .code_editor()
+                    .desired_width(f32::INFINITY)
+                    .desired_rows(10),
+            );
+            if !state.focused {
+                response.request_focus();
+                state.focused = true;
+            }
+        });
+
+    outcome
+}
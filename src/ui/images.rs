@@ -0,0 +1,201 @@
+use crate::thumbnail;
+use eframe::egui;
+use egui::{ColorImage, TextureHandle};
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+};
+use tracing::warn;
+
+/// Welche Fassung eines Bildes geladen wird
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImageSize {
+    /// Vorschaubild aus dem Disk-Cache (wird bei Bedarf erzeugt)
+    Thumbnail,
+    /// Originalbild für die Vorschau
+    Full,
+}
+
+/// Ergebnis des Hintergrund-Threads; `None` bei Lese- oder Dekodierfehlern
+struct Loaded {
+    path: PathBuf,
+    size: ImageSize,
+    image: Option<ColorImage>,
+}
+
+/// Lädt und dekodiert Bilder auf einem Worker-Thread, damit die GUI nie blockiert
+pub struct ImageLoader {
+    requests: Sender<(PathBuf, ImageSize)>,
+    results: Receiver<Loaded>,
+    pending: HashSet<(PathBuf, ImageSize)>,
+}
+
+impl ImageLoader {
+    pub fn new(ctx: egui::Context) -> Self {
+        let (request_tx, request_rx) = mpsc::channel::<(PathBuf, ImageSize)>();
+        let (result_tx, result_rx) = mpsc::channel();
+
+        // Thread endet, sobald der Loader (und damit der Sender) gedroppt wird
+        thread::spawn(move || {
+            for (path, size) in request_rx {
+                let image = decode(&path, size);
+                if result_tx.send(Loaded { path, size, image }).is_err() {
+                    break;
+                }
+                ctx.request_repaint();
+            }
+        });
+
+        Self {
+            requests: request_tx,
+            results: result_rx,
+            pending: HashSet::new(),
+        }
+    }
+
+    /// Stellt ein Bild in die Warteschlange (mehrfache Anfragen werden zusammengefasst)
+    pub fn request(&mut self, path: &Path, size: ImageSize) {
+        let key = (path.to_path_buf(), size);
+        if self.pending.insert(key.clone()) {
+            let _ = self.requests.send(key);
+        }
+    }
+
+    /// Lädt fertig dekodierte Bilder als Texturen in die passenden Caches
+    pub fn upload_finished(
+        &mut self,
+        ctx: &egui::Context,
+        thumbnails: &mut TextureCache,
+        full: &mut TextureCache,
+    ) {
+        while let Ok(loaded) = self.results.try_recv() {
+            self.pending.remove(&(loaded.path.clone(), loaded.size));
+
+            let image = loaded.image.unwrap_or_else(|| {
+                // 1x1 transparentes Bild als Platzhalter, damit nicht endlos neu geladen wird
+                ColorImage::from_rgba_unmultiplied([1, 1], &[0, 0, 0, 0])
+            });
+            let texture = ctx.load_texture(
+                format!("{:?}:{}", loaded.size, loaded.path.to_string_lossy()),
+                image,
+                egui::TextureOptions::default(),
+            );
+
+            match loaded.size {
+                ImageSize::Thumbnail => thumbnails.insert(loaded.path, texture),
+                ImageSize::Full => full.insert(loaded.path, texture),
+            }
+        }
+    }
+}
+
+fn decode(path: &Path, size: ImageSize) -> Option<ColorImage> {
+    let source = match size {
+        // Bereits verkleinert, kein Thumbnail vom Thumbnail erzeugen
+        ImageSize::Thumbnail if thumbnail::is_thumbnail(path) => path.to_path_buf(),
+        ImageSize::Thumbnail => match thumbnail::ensure(path) {
+            Ok(thumb) => thumb,
+            Err(e) => {
+                warn!(path = %path.display(), "Vorschaubild fehlgeschlagen: {e}");
+                return None;
+            }
+        },
+        ImageSize::Full => path.to_path_buf(),
+    };
+
+    match image::open(&source) {
+        Ok(img) => {
+            let img = img.to_rgba8();
+            let dims = [img.width() as usize, img.height() as usize];
+            Some(ColorImage::from_rgba_unmultiplied(
+                dims,
+                img.as_flat_samples().as_slice(),
+            ))
+        }
+        Err(e) => {
+            warn!(path = %source.display(), "Bild nicht dekodierbar: {e}");
+            None
+        }
+    }
+}
+
+/// Texturen mit fester Obergrenze; der am längsten nicht genutzte Eintrag fliegt zuerst raus
+pub struct TextureCache {
+    capacity: usize,
+    tick: u64,
+    entries: HashMap<PathBuf, (TextureHandle, u64)>,
+}
+
+impl TextureCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            tick: 0,
+            entries: HashMap::new(),
+        }
+    }
+
+    pub fn get(&mut self, path: &Path) -> Option<TextureHandle> {
+        self.tick += 1;
+        let tick = self.tick;
+        self.entries.get_mut(path).map(|(texture, used)| {
+            *used = tick;
+            texture.clone()
+        })
+    }
+
+    pub fn insert(&mut self, path: PathBuf, texture: TextureHandle) {
+        self.tick += 1;
+        self.entries.insert(path, (texture, self.tick));
+
+        while self.entries.len() > self.capacity {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, used))| *used)
+                .map(|(path, _)| path.clone());
+            match oldest {
+                Some(path) => {
+                    self.entries.remove(&path);
+                }
+                None => break,
+            }
+        }
+    }
+
+    /// Entfernt Texturen, deren Bilder nicht mehr im Verlauf stehen
+    pub fn retain(&mut self, keep: impl Fn(&Path) -> bool) {
+        self.entries.retain(|path, _| keep(path));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texture(ctx: &egui::Context, name: &str) -> TextureHandle {
+        ctx.load_texture(
+            name,
+            ColorImage::new([1, 1], egui::Color32::WHITE),
+            egui::TextureOptions::default(),
+        )
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let ctx = egui::Context::default();
+        let mut cache = TextureCache::new(2);
+        cache.insert("a".into(), texture(&ctx, "a"));
+        cache.insert("b".into(), texture(&ctx, "b"));
+
+        // "a" benutzen, damit "b" der älteste Eintrag ist
+        assert!(cache.get(Path::new("a")).is_some());
+        cache.insert("c".into(), texture(&ctx, "c"));
+
+        assert!(cache.get(Path::new("a")).is_some());
+        assert!(cache.get(Path::new("b")).is_none());
+        assert!(cache.get(Path::new("c")).is_some());
+    }
+}
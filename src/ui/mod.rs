@@ -1,34 +1,57 @@
-use crate::{error::AnyResult, history::History};
-use eframe::{egui, icon_data::from_png_bytes, NativeOptions};
+use crate::{
+    config::Config,
+    error::{Error, Result},
+    history::History,
+};
+use eframe::{icon_data::from_png_bytes, NativeOptions};
 use include_bytes_plus::include_bytes;
-use std::{
-    path::PathBuf,
-    sync::{Arc, Mutex},
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
 };
 
 mod app;
+mod editor;
+mod images;
+mod preview;
+mod snippets;
+mod transform_picker;
+mod window;
 use app::HyprclipApp;
 
-pub fn launch_with_history(history: Arc<Mutex<History>>, storage_path: PathBuf) -> AnyResult<()> {
+/// Startet den Picker und blockiert bis zum Schließen.
+///
+/// Gibt `true` zurück, wenn „Auswählen und Einfügen“ gewählt wurde.
+pub fn launch_with_history(history: Arc<Mutex<History>>, cfg: &Config) -> Result<bool> {
     // Icon laden (als Byte-Array – kein image crate nötig!)
     let icon_bytes = include_bytes!("assets/icon.png");
     let icon = from_png_bytes(&icon_bytes)?;
 
+    // Zielposition vor dem Öffnen bestimmen, solange der Mauszeiger noch dort ist
+    let position = window::initial_position(&cfg.window);
+
     let options = NativeOptions {
-        viewport: egui::ViewportBuilder::default()
-            .with_title("Hyprclip")
-            .with_inner_size([500.0, 300.0])
-            .with_resizable(false)
-            .with_decorations(false)
-            .with_always_on_top()
-            .with_icon(icon),
+        viewport: window::viewport(&cfg.window, position).with_icon(icon),
         ..Default::default()
     };
 
+    let cfg = cfg.clone();
+    let paste_requested = Arc::new(AtomicBool::new(false));
+    let app_paste_requested = Arc::clone(&paste_requested);
     eframe::run_native(
-        "Hyprclip",
+        window::WINDOW_TITLE,
         options,
-        Box::new(move |_cc| Ok(Box::new(HyprclipApp::new(history, storage_path)))),
+        Box::new(move |cc| {
+            Ok(Box::new(HyprclipApp::new(
+                &cc.egui_ctx,
+                history,
+                &cfg,
+                position,
+                app_paste_requested,
+            )))
+        }),
     )
-    .map_err(|e| format!("GUI konnte nicht gestartet werden: {e}").into())
+    .map_err(|e| Error::Command(format!("GUI konnte nicht gestartet werden: {e}")))?;
+
+    Ok(paste_requested.load(Ordering::SeqCst))
 }
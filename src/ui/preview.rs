@@ -0,0 +1,180 @@
+use crate::history::{ClipboardItem, Entry};
+use eframe::egui;
+use egui::TextureHandle;
+use std::path::Path;
+
+/// Darstellungsoptionen der Vorschau, bleiben zwischen Einträgen erhalten
+pub struct PreviewState {
+    pub monospace: bool,
+    pub show_whitespace: bool,
+    pub highlight: bool,
+    /// `None` = Bild in den verfügbaren Platz einpassen
+    pub zoom: Option<f32>,
+}
+
+impl Default for PreviewState {
+    fn default() -> Self {
+        Self {
+            monospace: true,
+            show_whitespace: false,
+            highlight: true,
+            zoom: None,
+        }
+    }
+}
+
+const MIN_ZOOM: f32 = 0.05;
+const MAX_ZOOM: f32 = 16.0;
+
+/// Zeichnet die Vorschau für einen Eintrag; Bilder brauchen die bereits geladene Textur
+pub fn show(
+    ui: &mut egui::Ui,
+    state: &mut PreviewState,
+    entry: &Entry,
+    texture: Option<&TextureHandle>,
+) {
+    match (&entry.item, texture) {
+        (ClipboardItem::Image(path), Some(texture)) => show_image(ui, state, path, texture),
+        (ClipboardItem::Image(path), None) => {
+            ui.label(format!("Bild nicht verfügbar: {}", path.display()));
+        }
+        (ClipboardItem::Text(text), _) => show_text(ui, state, text, entry.kind.language()),
+    }
+}
+
+fn show_text(ui: &mut egui::Ui, state: &mut PreviewState, text: &str, language: Option<&str>) {
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut state.monospace, "Monospace");
+        ui.checkbox(&mut state.show_whitespace, "Leerzeichen");
+        if let Some(lang) = language {
+            ui.checkbox(&mut state.highlight, format!("Highlighting ({lang})"));
+        }
+    });
+    ui.label(format!(
+        "{} Zeilen · {} Zeichen · {} Bytes",
+        text.lines().count().max(1),
+        text.chars().count(),
+        text.len()
+    ));
+    ui.separator();
+
+    // Syntax-Highlighting (gecacht durch egui_extras) – nicht kombinierbar mit der Leerzeichen-Anzeige
+    if let Some(lang) = language.filter(|_| state.highlight && !state.show_whitespace) {
+        let theme = egui_extras::syntax_highlighting::CodeTheme::from_style(ui.style());
+        let job =
+            egui_extras::syntax_highlighting::highlight(ui.ctx(), ui.style(), &theme, text, lang);
+        egui::ScrollArea::both()
+            .id_salt("preview_text")
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                ui.add(egui::Label::new(job).extend());
+            });
+        return;
+    }
+
+    let shown = if state.show_whitespace {
+        visualize_whitespace(text)
+    } else {
+        text.to_string()
+    };
+    let mut rich = egui::RichText::new(shown);
+    if state.monospace {
+        rich = rich.monospace();
+    }
+
+    egui::ScrollArea::both()
+        .id_salt("preview_text")
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            ui.add(egui::Label::new(rich).extend());
+        });
+}
+
+fn show_image(ui: &mut egui::Ui, state: &mut PreviewState, path: &Path, texture: &TextureHandle) {
+    let size = texture.size_vec2();
+    let file_size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+
+    ui.horizontal(|ui| {
+        ui.label(format!(
+            "{} × {} px · {}",
+            size.x as u32,
+            size.y as u32,
+            format_bytes(file_size)
+        ));
+        if ui.button("Einpassen").clicked() {
+            state.zoom = None;
+        }
+        if ui.button("1:1").clicked() {
+            state.zoom = Some(1.0);
+        }
+    });
+    ui.separator();
+
+    let available = ui.available_size();
+    let fit = (available.x / size.x).min(available.y / size.y).min(1.0);
+    let mut zoom = state.zoom.unwrap_or(fit);
+
+    egui::ScrollArea::both()
+        .id_salt("preview_image")
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            // Verschieben per Ziehen übernimmt die ScrollArea
+            let response = ui.add(egui::Image::new(texture).fit_to_exact_size(size * zoom));
+
+            // Ctrl + Mausrad / Pinch zoomt
+            if response.hovered() {
+                let delta = ui.input(|i| i.zoom_delta());
+                if delta != 1.0 {
+                    zoom = (zoom * delta).clamp(MIN_ZOOM, MAX_ZOOM);
+                    state.zoom = Some(zoom);
+                }
+            }
+        });
+}
+
+/// Macht Leerzeichen, Tabs und Zeilenumbrüche sichtbar
+pub fn visualize_whitespace(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            ' ' => out.push('·'),
+            '\t' => out.push_str("→   "),
+            '\r' => out.push('␍'),
+            '\n' => out.push_str("↵\n"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn whitespace_is_visualized() {
+        assert_eq!(visualize_whitespace("a b\tc\r\n"), "a·b→   c␍↵\n");
+    }
+
+    #[test]
+    fn bytes_are_human_readable() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(2048), "2.0 KiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.0 MiB");
+    }
+}
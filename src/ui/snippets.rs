@@ -0,0 +1,122 @@
+use crate::snippets::{Snippet, SnippetStore};
+use eframe::egui;
+use egui::{Key, Modifiers};
+use std::collections::HashMap;
+
+/// Ergebnis des Eingabeformulars
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormOutcome {
+    Insert,
+    Cancel,
+}
+
+/// Abfrage der `{input:…}`-Platzhalter vor dem Einfügen
+pub struct InputForm {
+    /// Index in [`SnippetStore::snippets`]
+    pub index: usize,
+    /// Nach dem Einfügen auch den Tastendruck auslösen
+    pub paste: bool,
+    pub values: Vec<(String, String)>,
+    focused: bool,
+}
+
+impl InputForm {
+    pub fn new(index: usize, snippet: &Snippet, paste: bool) -> Self {
+        Self {
+            index,
+            paste,
+            values: snippet
+                .inputs()
+                .into_iter()
+                .map(|name| (name, String::new()))
+                .collect(),
+            focused: false,
+        }
+    }
+
+    pub fn inputs(&self) -> HashMap<String, String> {
+        self.values.iter().cloned().collect()
+    }
+}
+
+/// Liste der Snippets; `visible` enthält die Indizes passend zur Suche
+pub fn show_list(
+    ui: &mut egui::Ui,
+    store: &SnippetStore,
+    visible: &[usize],
+    selected: usize,
+) -> Option<usize> {
+    let mut clicked = None;
+    egui::ScrollArea::vertical()
+        .id_salt("snippets")
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            for (pos, &index) in visible.iter().enumerate() {
+                let snippet = &store.snippets[index];
+                let sel = pos == selected;
+                let tags: String = snippet.tags.iter().map(|t| format!(" #{t}")).collect();
+                let row = ui
+                    .selectable_label(
+                        sel,
+                        format!("🧩 {}{tags}  {}", snippet.name, snippet.description),
+                    )
+                    .on_hover_text(&snippet.content);
+                if sel {
+                    row.scroll_to_me(None);
+                }
+                if row.clicked() {
+                    clicked = Some(pos);
+                }
+            }
+        });
+    clicked
+}
+
+/// `Enter` fügt ein, `Tab` springt zum nächsten Feld, `Escape` bricht ab
+pub fn show_form(
+    ui: &mut egui::Ui,
+    form: &mut InputForm,
+    snippet: &Snippet,
+) -> Option<FormOutcome> {
+    let mut outcome = ui.input_mut(|i| {
+        if i.consume_key(Modifiers::NONE, Key::Escape) {
+            Some(FormOutcome::Cancel)
+        } else if i.consume_key(Modifiers::NONE, Key::Enter) {
+            Some(FormOutcome::Insert)
+        } else {
+            None
+        }
+    });
+
+    ui.heading(format!("🧩 {}", snippet.name));
+    if !snippet.description.is_empty() {
+        ui.label(&snippet.description);
+    }
+    ui.separator();
+
+    egui::Grid::new("snippet_inputs")
+        .num_columns(2)
+        .show(ui, |ui| {
+            for (pos, (name, value)) in form.values.iter_mut().enumerate() {
+                ui.label(name.as_str());
+                let response =
+                    ui.add(egui::TextEdit::singleline(value).desired_width(f32::INFINITY));
+                if pos == 0 && !form.focused {
+                    response.request_focus();
+                    form.focused = true;
+                }
+                ui.end_row();
+            }
+        });
+
+    ui.separator();
+    ui.horizontal(|ui| {
+        if ui.button("Einfügen (Enter)").clicked() {
+            outcome = Some(FormOutcome::Insert);
+        }
+        if ui.button("Abbrechen (Esc)").clicked() {
+            outcome = Some(FormOutcome::Cancel);
+        }
+    });
+    outcome
+}
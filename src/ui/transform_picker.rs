@@ -0,0 +1,96 @@
+use crate::transform::{Transform, Transforms};
+use eframe::egui;
+use egui::{Key, Modifiers};
+
+/// Ergebnis der Auswahl
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PickerOutcome {
+    /// Name der gewählten Umwandlung
+    Apply(String),
+    Cancel,
+}
+
+/// Auswahlliste der Umwandlungen für einen Eintrag
+pub struct PickerState {
+    /// [`crate::history::Entry::key`] des umzuwandelnden Eintrags
+    pub key: u64,
+    filter: String,
+    selected: usize,
+    focused: bool,
+}
+
+impl PickerState {
+    pub fn new(key: u64) -> Self {
+        Self {
+            key,
+            filter: String::new(),
+            selected: 0,
+            focused: false,
+        }
+    }
+}
+
+/// Tippen filtert, Pfeiltasten wählen, `Enter` wendet an, `Escape` bricht ab
+pub fn show(
+    ui: &mut egui::Ui,
+    state: &mut PickerState,
+    transforms: &Transforms,
+) -> Option<PickerOutcome> {
+    let filter = state.filter.to_lowercase();
+    let matching: Vec<&dyn Transform> = transforms
+        .iter()
+        .filter(|t| t.name().contains(&filter) || t.description().to_lowercase().contains(&filter))
+        .collect();
+    state.selected = state.selected.min(matching.len().saturating_sub(1));
+
+    let mut outcome = ui.input_mut(|i| {
+        if i.consume_key(Modifiers::NONE, Key::ArrowDown) {
+            state.selected = (state.selected + 1).min(matching.len().saturating_sub(1));
+        }
+        if i.consume_key(Modifiers::NONE, Key::ArrowUp) {
+            state.selected = state.selected.saturating_sub(1);
+        }
+        if i.consume_key(Modifiers::NONE, Key::Escape) {
+            Some(PickerOutcome::Cancel)
+        } else if i.consume_key(Modifiers::NONE, Key::Enter) {
+            matching
+                .get(state.selected)
+                .map(|t| PickerOutcome::Apply(t.name().to_string()))
+        } else {
+            None
+        }
+    });
+
+    ui.heading("🔀 Umwandeln");
+    let response = ui.add(
+        egui::TextEdit::singleline(&mut state.filter)
+            .hint_text("Filter…")
+            .desired_width(f32::INFINITY),
+    );
+    if !state.focused {
+        response.request_focus();
+        state.focused = true;
+    }
+    if response.changed() {
+        state.selected = 0;
+    }
+    ui.separator();
+
+    egui::ScrollArea::vertical()
+        .id_salt("transform_picker")
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            for (pos, t) in matching.iter().enumerate() {
+                let sel = pos == state.selected;
+                let row = ui.selectable_label(sel, format!("{:<20} {}", t.name(), t.description()));
+                if sel {
+                    row.scroll_to_me(None);
+                }
+                if row.clicked() {
+                    outcome = Some(PickerOutcome::Apply(t.name().to_string()));
+                }
+            }
+        });
+
+    outcome
+}
@@ -0,0 +1,152 @@
+use crate::{
+    config::{WindowAnchor, WindowConfig},
+    hyprland,
+};
+use eframe::egui;
+
+/// Fensterklasse (Wayland `app_id`) – damit lassen sich zuverlässige Window-Rules schreiben
+pub const WINDOW_CLASS: &str = "hyprclip";
+/// Fenstertitel des Pickers
+pub const WINDOW_TITLE: &str = "Hyprclip";
+
+/// Rechteck in globalen, logischen Koordinaten
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Area {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+/// Berechnet die linke obere Ecke des Fensters für den konfigurierten Anker.
+///
+/// Das Ergebnis wird immer in den Monitor geklemmt, damit das Fenster
+/// auch am Bildschirmrand vollständig sichtbar bleibt.
+pub fn anchor_position(
+    cfg: &WindowConfig,
+    monitor: Area,
+    cursor: Option<(f32, f32)>,
+) -> (f32, f32) {
+    let (x, y) = match (cfg.anchor, cursor) {
+        (WindowAnchor::Cursor, Some((cx, cy))) => (cx + cfg.margin, cy + cfg.margin),
+        (WindowAnchor::Top, _) => (
+            monitor.x + (monitor.width - cfg.width) / 2.0,
+            monitor.y + cfg.margin,
+        ),
+        // Center – und Fallback, wenn keine Mausposition verfügbar ist
+        _ => (
+            monitor.x + (monitor.width - cfg.width) / 2.0,
+            monitor.y + (monitor.height - cfg.height) / 2.0,
+        ),
+    };
+
+    let max_x = (monitor.x + monitor.width - cfg.width).max(monitor.x);
+    let max_y = (monitor.y + monitor.height - cfg.height).max(monitor.y);
+    (x.clamp(monitor.x, max_x), y.clamp(monitor.y, max_y))
+}
+
+/// Ermittelt die Zielposition über Hyprland (fokussierter Monitor + Mauszeiger)
+pub fn initial_position(cfg: &WindowConfig) -> Option<(f32, f32)> {
+    let monitor = hyprland::focused_monitor()?;
+    let (width, height) = monitor.logical_size();
+    let area = Area {
+        x: monitor.x as f32,
+        y: monitor.y as f32,
+        width,
+        height,
+    };
+
+    let cursor = match cfg.anchor {
+        WindowAnchor::Cursor => hyprland::cursor_pos(),
+        _ => None,
+    };
+
+    Some(anchor_position(cfg, area, cursor))
+}
+
+/// Baut den Viewport für den Picker aus der Konfiguration
+pub fn viewport(cfg: &WindowConfig, position: Option<(f32, f32)>) -> egui::ViewportBuilder {
+    let mut builder = egui::ViewportBuilder::default()
+        .with_title(WINDOW_TITLE)
+        .with_app_id(WINDOW_CLASS)
+        .with_inner_size([cfg.width, cfg.height])
+        .with_resizable(false)
+        .with_decorations(false)
+        .with_always_on_top();
+
+    if let Some(pos) = position {
+        builder = builder.with_position(pos);
+    }
+
+    builder
+}
+
+/// Macht das Fenster unter Hyprland schwebend und verschiebt es an die Zielposition.
+///
+/// winit unterstützt kein wlr-layer-shell und Wayland ignoriert Positionswünsche
+/// von Toplevels, deshalb wird die Platzierung über Dispatcher erledigt.
+pub fn place_on_hyprland(cfg: &WindowConfig, position: Option<(f32, f32)>) {
+    if !hyprland::is_running() {
+        return;
+    }
+
+    let target = format!("pid:{}", std::process::id());
+    let mut commands = vec![
+        format!("setfloating {target}"),
+        format!(
+            "resizewindowpixel exact {} {},{target}",
+            cfg.width as i32, cfg.height as i32
+        ),
+    ];
+    if let Some((x, y)) = position {
+        commands.push(format!(
+            "movewindowpixel exact {} {},{target}",
+            x as i32, y as i32
+        ));
+    }
+
+    if let Err(e) = hyprland::dispatch_batch(&commands) {
+        tracing::warn!("Fenster konnte nicht platziert werden: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MONITOR: Area = Area {
+        x: 1920.0,
+        y: 0.0,
+        width: 1920.0,
+        height: 1080.0,
+    };
+
+    fn cfg(anchor: WindowAnchor) -> WindowConfig {
+        WindowConfig {
+            width: 500.0,
+            height: 300.0,
+            anchor,
+            margin: 10.0,
+            close_on_focus_loss: true,
+        }
+    }
+
+    #[test]
+    fn center_and_top_use_monitor_offset() {
+        let center = anchor_position(&cfg(WindowAnchor::Center), MONITOR, None);
+        assert_eq!(center, (1920.0 + 710.0, 390.0));
+
+        let top = anchor_position(&cfg(WindowAnchor::Top), MONITOR, None);
+        assert_eq!(top, (1920.0 + 710.0, 10.0));
+    }
+
+    #[test]
+    fn cursor_anchor_is_clamped_to_monitor() {
+        let pos = anchor_position(&cfg(WindowAnchor::Cursor), MONITOR, Some((3800.0, 1000.0)));
+        assert_eq!(pos, (3840.0 - 500.0, 1080.0 - 300.0));
+
+        // ohne Mausposition wird zentriert
+        let fallback = anchor_position(&cfg(WindowAnchor::Cursor), MONITOR, None);
+        assert_eq!(fallback, (1920.0 + 710.0, 390.0));
+    }
+}
@@ -36,11 +36,4 @@ mod tests {
             "Hashes für verschiedene Inputs sollten unterschiedlich sein."
         );
     }
-
-    #[test]
-    fn test_hash_data_u64_range() {
-        let input = "some data";
-        let h = hash_data(&input);
-        assert!(h <= u64::MAX, "Hash sollte innerhalb von u64 liegen.");
-    }
 }
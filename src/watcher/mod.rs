@@ -1,9 +1,9 @@
-use crate::error::AnyResult;
+use crate::error::Result;
 use std::io::Read;
 use wl_clipboard_rs::paste::{get_contents, ClipboardType, MimeType, Seat};
 
 /// Gibt den aktuellen Text im Wayland-Clipboard zurück.
-pub fn get_latest_entry() -> AnyResult<String> {
+pub fn get_latest_entry() -> Result<String> {
     let (mut pipe, _mime) =
         get_contents(ClipboardType::Regular, Seat::Unspecified, MimeType::Text)?;
     let mut buf = String::new();
@@ -1,24 +1,38 @@
-use crate::error::AnyResult;
+use crate::error::{Error, Result};
+use crate::logging::Redacted;
 use crate::util::hash_data;
 use crate::{
     clipboard_state,
-    config::Config,
+    config::{Config, ConfigWatcher},
     history::{ClipboardItem, History},
+    hooks::{self, FilterChain, Filtered, HookEvent, HookRunner},
+    notifications::Notifier,
+    queue,
+    service::Watchdog,
+    state::WatcherState,
+    thumbnail,
 };
 use chrono::Local;
-use image::{ImageBuffer, Rgba};
+use image::{DynamicImage, ImageBuffer, Rgba};
 use std::{
     fs,
     io::Read,
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::{Arc, Mutex},
     time::{Duration, Instant},
 };
-use tokio::time::sleep;
-use wl_clipboard_rs::paste::{get_contents, ClipboardType, MimeType, Seat};
-
-pub async fn watch_clipboard(history: Arc<Mutex<History>>, config: Config) {
-    println!("📋 Async Clipboard-Watcher (Wayland) gestartet...");
+use tokio::{sync::watch, time::sleep};
+use tracing::{debug, error, info, trace, warn};
+use wl_clipboard_rs::paste::{get_contents, get_mime_types, ClipboardType, MimeType, Seat};
+
+/// Überwacht das Clipboard; neu geladene Konfigurationen gehen an `reloads` (D-Bus).
+/// Kehrt nur mit einem Fehler beim Start zurück.
+pub async fn watch_clipboard(
+    history: Arc<Mutex<History>>,
+    mut config: Config,
+    reloads: watch::Sender<Config>,
+) -> Result<()> {
+    info!("Clipboard-Watcher (Wayland) gestartet");
 
     let mut last_text_hash: Option<u64> = None;
     let mut last_image_hash: Option<u64> = None;
@@ -27,14 +41,56 @@ pub async fn watch_clipboard(history: Arc<Mutex<History>>, config: Config) {
     let debounce_delay = Duration::from_millis(500);
     let mut last_item: Option<ClipboardItem> = None;
 
-    let image_dir = PathBuf::from(&config.image_storage_path);
-    fs::create_dir_all(&image_dir).expect("📁 Bildverzeichnis konnte nicht erstellt werden.");
+    let mut hooks = HookRunner::new(&config.hooks);
+    let mut filters = FilterChain::new(&config.hooks.filters, config.hooks.filter_timeout_ms);
+
+    let mut queue_path = queue::Queue::path(&config.storage_path);
+    let mut state_path = WatcherState::path(&config.storage_path);
+    let mut notifier = Notifier::new(&config.notifications).await;
+    let mut was_paused = WatcherState::load(&state_path).paused;
+    let mut watchdog = Watchdog::from_env();
+    let mut image_dir = PathBuf::from(&config.image_storage_path);
+    fs::create_dir_all(&image_dir).map_err(Error::storage(&image_dir))?;
+
+    let mut config_watcher = ConfigWatcher::new(|| {});
 
     loop {
+        // Jede Runde zählt als Lebenszeichen – hängt die Schleife, startet systemd neu
+        watchdog.ping();
+
+        // ⚙️ Geänderte config.toml übernehmen; eine ungültige Datei ändert nichts
+        if config_watcher.poll() {
+            match Config::load() {
+                Ok(new) => {
+                    hooks = HookRunner::new(&new.hooks);
+                    filters = FilterChain::new(&new.hooks.filters, new.hooks.filter_timeout_ms);
+                    queue_path = queue::Queue::path(&new.storage_path);
+                    state_path = WatcherState::path(&new.storage_path);
+                    notifier = Notifier::new(&new.notifications).await;
+                    image_dir = new.image_storage_path.clone();
+                    if let Err(e) = fs::create_dir_all(&image_dir) {
+                        warn!(path = %image_dir.display(), "Bildverzeichnis nicht anlegbar: {e}");
+                    }
+                    // Verlauf nur bei neuem Pfad laden; sonst genügt das neue Limit, das beim
+                    // nächsten Eintrag greift
+                    if new.storage_path != config.storage_path {
+                        *history.lock().unwrap() =
+                            History::load(&new.storage_path, new.history_limit);
+                    } else {
+                        history.lock().unwrap().limit = new.history_limit;
+                    }
+                    reloads.send_replace(new.clone());
+                    config = new;
+                    info!("Konfiguration neu geladen");
+                }
+                Err(e) => error!("{e} – bisherige Konfiguration bleibt aktiv"),
+            }
+        }
+
         // ✅ 1. Ignore prüfen (timestamp-based)
         if clipboard_state::should_ignore_recently(Duration::from_millis(500)) {
             // Änderung stammt von uns selbst → ignorieren
-            println!("⚠️ Ignoriere Clipboard-Event wegen kürzlichem self-set.");
+            trace!("Ignoriere eigene Clipboard-Änderung");
             sleep(Duration::from_millis(200)).await;
             continue;
         }
@@ -42,44 +98,108 @@ pub async fn watch_clipboard(history: Arc<Mutex<History>>, config: Config) {
         if last_item.is_some() && clipboard_is_empty() {
             if let Some(item) = &last_item {
                 if let Err(e) = crate::clipboard::set_clipboard_item(item) {
-                    eprintln!("⚠️ Fehler beim erneuten Setzen des Clipboards: {}", e);
+                    warn!("Clipboard konnte nicht wiederhergestellt werden: {e}");
                 } else {
-                    println!("🔄 Clipboard wiederhergestellt.");
+                    debug!("Clipboard wiederhergestellt");
                 }
             }
             sleep(Duration::from_millis(200)).await;
             continue;
         }
 
+        // ⏸️ Pausiert bzw. vertraulich: aktuellen Inhalt nur als bekannt merken,
+        // damit er nach dem Fortsetzen nicht nachträglich gespeichert wird
+        let state = WatcherState::load(&state_path);
+        if state.paused != was_paused {
+            notifier.paused(state.paused);
+            was_paused = state.paused;
+        }
+        let sensitive = !state.paused && clipboard_is_sensitive();
+        if sensitive != state.sensitive {
+            if sensitive {
+                info!("Vertraulicher Inhalt (Passwortmanager) wird nicht gespeichert");
+                notifier.sensitive_skipped();
+            }
+            if let Err(e) = WatcherState::update(&state_path, |s| s.sensitive = sensitive) {
+                error!("Watcher-Zustand konnte nicht gespeichert werden: {e}");
+            }
+        }
+        if state.paused || sensitive {
+            last_text_hash = get_clipboard_text().map(|text| hash_data(&text));
+            last_image_hash = get_clipboard_image().map(|data| hash_data(&data));
+            sleep(Duration::from_millis(200)).await;
+            continue;
+        }
+
         let now = Instant::now();
 
         // ✅ 2. TEXT
         if let Some(text) = get_clipboard_text() {
             let hash = hash_data(&text);
 
-            let mut hist_guard = history.lock().unwrap();
-            let is_duplicate = hist_guard.entries.iter().any(|e| e.hash == Some(hash));
-            let limit = hist_guard.limit;
+            // Sperre nicht über `.await` halten (Filter laufen asynchron)
+            let (is_duplicate, limit) = {
+                let hist_guard = history.lock().unwrap();
+                let is_duplicate = hist_guard.entries.iter().any(|e| e.hash == Some(hash));
+                (is_duplicate, hist_guard.limit)
+            };
 
             if Some(hash) != last_text_hash
                 && !is_duplicate
                 && now.duration_since(last_text_change) >= debounce_delay
             {
-                println!("📝 Neuer Texteingang: {}", text);
+                info!(hash = %format!("{hash:x}"), chars = text.chars().count(), "Neuer Text");
+                trace!(text = %Redacted(&text), "Inhalt");
                 last_text_hash = Some(hash);
                 last_text_change = now;
 
+                let source_app = if needs_source_app(&hooks, &filters) {
+                    hooks::source_app().await
+                } else {
+                    None
+                };
+                let text = match filters.filter_text(text, source_app.clone()).await {
+                    Filtered::Store(text) => text,
+                    Filtered::Veto(name) => {
+                        info!(filter = %name, "Text von Filter verworfen");
+                        sleep(Duration::from_millis(200)).await;
+                        continue;
+                    }
+                };
+                // Der umgeschriebene Text landet gleich im Clipboard – als bekannt merken,
+                // sonst sammelt die nächste Runde ihn als Duplikat ein zweites Mal ein
+                last_text_hash = Some(hash_data(&text));
+
                 let mut hist = History::load(&config.storage_path, limit);
                 hist.add_text(text.clone());
                 if let Err(err) = hist.save(&config.storage_path) {
-                    eprintln!("⚠️ Fehler beim Speichern (Text): {}", err);
+                    error!("Verlauf konnte nicht gespeichert werden: {err}");
+                    notifier.error(&format!("Verlauf konnte nicht gespeichert werden: {err}"));
+                }
+                run_hooks(&hooks, &hist, source_app);
+                if let Some(entry) = hist.entries.first() {
+                    notifier.new_entry(entry);
                 }
 
-                *hist_guard = hist;
+                *history.lock().unwrap() = hist;
 
+                // Gefilterter Text ersetzt auch den Clipboard-Inhalt
+                let item = ClipboardItem::Text(text.clone());
+                queue::collect(&queue_path, &item);
+                if let Err(e) = crate::clipboard::set_clipboard_item(&item) {
+                    warn!("Text konnte nicht ins Clipboard gesetzt werden: {e}");
+                } else {
+                    last_item = Some(item);
+                }
+            } else if Some(hash) != last_text_hash && is_duplicate {
+                // Bekannter Eintrag (z. B. aus GUI/CLI gewählt): Besitz übernehmen,
+                // damit er nach dem Ende des setzenden Prozesses erhalten bleibt
+                last_text_hash = Some(hash);
                 let item = ClipboardItem::Text(text.clone());
+                // Beim Formularausfüllen wiederholen sich Werte – auch Bekanntes sammeln
+                queue::collect(&queue_path, &item);
                 if let Err(e) = crate::clipboard::set_clipboard_item(&item) {
-                    eprintln!("⚠️ Fehler beim Setzen des Textes ins Clipboard: {}", e);
+                    warn!("Clipboard konnte nicht übernommen werden: {e}");
                 } else {
                     last_item = Some(item);
                 }
@@ -93,7 +213,7 @@ pub async fn watch_clipboard(history: Arc<Mutex<History>>, config: Config) {
             // ✅ Skip hash prüfen und konsumieren
             if let Some(skip_hash) = crate::clipboard_state::take_skip_image_hash() {
                 if skip_hash == hash {
-                    println!("⚠️ Skip Bild (skip_hash match: {:x})", hash);
+                    trace!(hash = %format!("{hash:x}"), "Eigenes Bild übersprungen");
                     sleep(Duration::from_millis(200)).await;
                     continue;
                 }
@@ -108,25 +228,48 @@ pub async fn watch_clipboard(history: Arc<Mutex<History>>, config: Config) {
                     .iter()
                     .any(|e| e.hash == Some(hash))
             {
-                println!("⚠️ Skip Bild (Hash {:x} bereits bekannt).", hash);
+                trace!(hash = %format!("{hash:x}"), "Bild bereits bekannt");
                 sleep(Duration::from_millis(200)).await;
                 continue;
             }
 
             if now.duration_since(last_image_change) >= debounce_delay {
-                println!("🖼️ Neues Bild erkannt (Hash: {:x})", hash);
+                info!(hash = %format!("{hash:x}"), bytes = image_data.len(), "Neues Bild");
                 last_image_hash = Some(hash);
                 last_image_change = now;
 
+                let source_app = if needs_source_app(&hooks, &filters) {
+                    hooks::source_app().await
+                } else {
+                    None
+                };
+                let image_data = match filters.filter_image(image_data, source_app.clone()).await {
+                    Filtered::Store(data) => data,
+                    Filtered::Veto(name) => {
+                        info!(filter = %name, "Bild von Filter verworfen");
+                        sleep(Duration::from_millis(200)).await;
+                        continue;
+                    }
+                };
+                // Dateiname, Eintrag und Skip-Hash gehören zum gespeicherten (gefilterten) Bild
+                let hash = hash_data(&image_data);
+                last_image_hash = Some(hash);
+
                 match save_image_as_png(&image_data, &image_dir, hash) {
                     Ok(path) => {
-                        println!("🖼️ Bild gespeichert unter {}", path.display());
+                        debug!(path = %path.display(), "Bild gespeichert");
 
                         let mut hist =
                             History::load(&config.storage_path, history.lock().unwrap().limit);
                         hist.add_image(path.clone(), hash);
                         if let Err(err) = hist.save(&config.storage_path) {
-                            eprintln!("⚠️ Fehler beim Speichern (Bild): {}", err);
+                            error!("Verlauf konnte nicht gespeichert werden: {err}");
+                            notifier
+                                .error(&format!("Verlauf konnte nicht gespeichert werden: {err}"));
+                        }
+                        run_hooks(&hooks, &hist, source_app);
+                        if let Some(entry) = hist.entries.first() {
+                            notifier.new_entry(entry);
                         }
 
                         *history.lock().unwrap() = hist;
@@ -139,13 +282,17 @@ pub async fn watch_clipboard(history: Arc<Mutex<History>>, config: Config) {
 
                         // ✅ Clipboard erneut setzen
                         let item = ClipboardItem::Image(path.clone());
+                        queue::collect(&queue_path, &item);
                         if let Err(e) = crate::clipboard::set_clipboard_item(&item) {
-                            eprintln!("⚠️ Fehler beim Setzen des Bildes ins Clipboard: {}", e);
+                            warn!("Bild konnte nicht ins Clipboard gesetzt werden: {e}");
                         } else {
                             last_item = Some(item);
                         }
                     }
-                    Err(e) => eprintln!("⚠️ Fehler beim Speichern des Bildes: {}", e),
+                    Err(e) => {
+                        error!("Bild konnte nicht gespeichert werden: {e}");
+                        notifier.error(&format!("Bild konnte nicht gespeichert werden: {e}"));
+                    }
                 }
             }
         }
@@ -154,12 +301,27 @@ pub async fn watch_clipboard(history: Arc<Mutex<History>>, config: Config) {
     }
 }
 
+// Quellanwendung nur abfragen, wenn Hooks oder Filter sie brauchen könnten
+fn needs_source_app(hooks: &HookRunner, filters: &FilterChain) -> bool {
+    !hooks.is_empty() || !filters.is_empty()
+}
+
+// 🪝 Hooks für den neuesten Eintrag im Hintergrund starten
+fn run_hooks(hooks: &HookRunner, history: &History, source_app: Option<String>) {
+    if hooks.is_empty() {
+        return;
+    }
+    if let Some(entry) = history.entries.first() {
+        hooks.dispatch(HookEvent::from_entry(entry, source_app));
+    }
+}
+
 fn get_clipboard_text() -> Option<String> {
     match get_contents(ClipboardType::Regular, Seat::Unspecified, MimeType::Text) {
         Ok((mut pipe, _)) => {
             let mut buf = String::new();
             if let Err(e) = pipe.read_to_string(&mut buf) {
-                eprintln!("⚠️ Fehler beim Lesen des Textes aus der Zwischenablage: {e}");
+                warn!("Text aus dem Clipboard nicht lesbar: {e}");
                 return None;
             }
 
@@ -170,20 +332,20 @@ fn get_clipboard_text() -> Option<String> {
 
             // 🔒 Variante 1: HTML mit <img> Tag ignorieren, um Endlosloop zu verhindern
             if trimmed.starts_with("<meta") && trimmed.contains("<img") {
-                println!("⚠️ Ignoriere HTML-Zwischenablage mit <img> Tag, um Loop zu verhindern.");
+                trace!("Ignoriere HTML mit <img>-Tag, um Endlosschleife zu verhindern");
                 return None;
             }
 
             // 🔒 Variante 2: "0,0" ignorieren
             if trimmed == "0,0" {
-                println!("⚠️ Ignoriere Zwischenablage-Eintrag '0,0' (Koordinaten-Placeholder).");
+                trace!("Ignoriere Koordinaten-Platzhalter '0,0'");
                 return None;
             }
 
             Some(buf.to_string())
         }
         Err(e) => {
-            eprintln!("⚠️ Fehler beim Zugriff auf die Zwischenablage (Text): {e}");
+            trace!("Kein Text im Clipboard: {e}");
             None
         }
     }
@@ -193,12 +355,12 @@ fn get_clipboard_image() -> Option<Vec<u8>> {
     match get_contents(
         ClipboardType::Regular,
         Seat::Unspecified,
-        MimeType::Specific("image/png".into()),
+        MimeType::Specific("image/png"),
     ) {
         Ok((mut pipe, _)) => {
             let mut data = Vec::new();
             if let Err(e) = pipe.read_to_end(&mut data) {
-                eprintln!("⚠️ Fehler beim Lesen des Bildes aus der Zwischenablage: {e}");
+                warn!("Bild aus dem Clipboard nicht lesbar: {e}");
                 return None;
             }
             if data.is_empty() {
@@ -208,21 +370,29 @@ fn get_clipboard_image() -> Option<Vec<u8>> {
             }
         }
         Err(e) => {
-            eprintln!("⚠️ Fehler beim Zugriff auf die Zwischenablage (Bild): {e}");
+            trace!("Kein Bild im Clipboard: {e}");
             None
         }
     }
 }
 
+/// Passwortmanager (KeePassXC, Bitwarden, …) markieren Geheimnisse mit diesem MIME-Typ
+const PASSWORD_MANAGER_HINT: &str = "x-kde-passwordManagerHint";
+
+fn clipboard_is_sensitive() -> bool {
+    get_mime_types(ClipboardType::Regular, Seat::Unspecified)
+        .is_ok_and(|types| types.contains(PASSWORD_MANAGER_HINT))
+}
+
 fn clipboard_is_empty() -> bool {
     get_clipboard_text().is_none() && get_clipboard_image().is_none()
 }
 
-fn save_image_as_png(data: &[u8], dir: &PathBuf, hash: u64) -> AnyResult<PathBuf> {
+fn save_image_as_png(data: &[u8], dir: &Path, hash: u64) -> Result<PathBuf> {
     let img = image::load_from_memory(data)?.to_rgba8();
     let buffer: ImageBuffer<Rgba<u8>, _> =
         ImageBuffer::from_raw(img.width(), img.height(), img.into_raw())
-            .ok_or("Ungültiges Bildformat")?;
+            .ok_or_else(|| Error::Invalid("Ungültiges Bildformat".into()))?;
 
     let filename = format!(
         "clip_{:x}_{}.png",
@@ -232,5 +402,10 @@ fn save_image_as_png(data: &[u8], dir: &PathBuf, hash: u64) -> AnyResult<PathBuf
 
     let path = dir.join(filename);
     buffer.save(&path)?;
+
+    // Vorschaubild gleich mit erzeugen, damit die GUI nichts dekodieren muss
+    if let Err(e) = thumbnail::write_for(&path, &DynamicImage::ImageRgba8(buffer)) {
+        warn!(path = %path.display(), "Vorschaubild konnte nicht erstellt werden: {e}");
+    }
     Ok(path)
 }